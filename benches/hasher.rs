@@ -0,0 +1,72 @@
+//! Compares lookups in symbol-keyed `HashMap`s using [`SymbolBuildHasher`] against the default
+//! (SipHash) hasher. Run with `cargo bench --features std`.
+
+use smol_symbol::*;
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::BuildHasher,
+    hint::black_box,
+    time::Instant,
+};
+
+const KEYS: usize = 10_000;
+const ROUNDS: usize = 100;
+
+/// Generates `count` distinct symbols of varying lengths.
+fn symbols(count: usize) -> Vec<Symbol> {
+    (0..count)
+        .map(|i| {
+            let mut name = String::from("sym_");
+            let mut n = i;
+            loop {
+                name.push((b'a' + (n % 26) as u8) as char);
+                n /= 26;
+                if n == 0 {
+                    break;
+                }
+            }
+            Symbol::try_from(name).unwrap()
+        })
+        .collect()
+}
+
+/// Prints the average time taken by `f` per iteration, over `iterations` iterations.
+fn bench(name: &str, iterations: usize, mut f: impl FnMut()) {
+    // warm up
+    f();
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    let elapsed = start.elapsed();
+    let per_op = elapsed.as_nanos() as f64 / (ROUNDS * iterations) as f64;
+    println!("{name:<40} {per_op:>8.2} ns/op");
+}
+
+fn bench_hasher<S: BuildHasher + Default>(name: &str, keys: &[Symbol], missing: &[Symbol]) {
+    bench(&format!("{name}: insert"), keys.len(), || {
+        let mut map = HashMap::with_capacity_and_hasher(keys.len(), S::default());
+        for (i, key) in keys.iter().enumerate() {
+            map.insert(*key, i);
+        }
+        black_box(map);
+    });
+    let map: HashMap<Symbol, usize, S> = keys.iter().copied().zip(0..).collect();
+    bench(&format!("{name}: get (hit)"), keys.len(), || {
+        for key in keys {
+            black_box(map.get(black_box(key)));
+        }
+    });
+    bench(&format!("{name}: get (miss)"), missing.len(), || {
+        for key in missing {
+            black_box(map.get(black_box(key)));
+        }
+    });
+}
+
+fn main() {
+    let all = symbols(KEYS * 2);
+    let (keys, missing) = all.split_at(KEYS);
+    bench_hasher::<RandomState>("SipHash (default)", keys, missing);
+    bench_hasher::<SymbolBuildHasher>("SymbolBuildHasher", keys, missing);
+}
@@ -1,29 +1,448 @@
 //! This crate houses the [`s!`] macro, used to create `Symbol` / `CustomSymbol` instances at
 //! const-eval time from a provided ident and (if applicable) `Alphabet`.
 
-use derive_syn_parse::Parse;
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, parse_quote, Ident, Token, TypePath};
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote};
+use std::collections::HashSet;
+use syn::{
+    braced, bracketed,
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse_macro_input, parse_quote,
+    punctuated::Punctuated,
+    token, Attribute, Data, DeriveInput, Error, Expr, Fields, Ident, Lit, LitChar, LitStr, Pat,
+    PatIdent, PatLit, Result, Token, TypePath,
+};
 
-#[derive(Parse)]
+/// An `Alphabet` whose characters are known to the macros in this crate ahead of time,
+/// allowing symbols that use it to be validated directly by the proc macro (with proper
+/// spans) rather than during const-eval.
+struct KnownAlphabet {
+    name: &'static str,
+    chars: &'static str,
+    /// Whether the alphabet uses the `fold_case` normalization rule.
+    fold_case: bool,
+    /// The replacement normalization rules of the alphabet, as `(alias, char)` pairs.
+    replacements: &'static [(char, char)],
+}
+
+/// The `Alphabet`s shipped with `smol-symbol`. These mirror the corresponding
+/// `custom_alphabet!` invocations in the main crate, which uses [`__check_known_alphabets`]
+/// to ensure the two stay in sync.
+const KNOWN_ALPHABETS: &[KnownAlphabet] = &[
+    KnownAlphabet {
+        name: "DefaultAlphabet",
+        chars: "abcdefghijklmnopqrstuvwxyz_",
+        fold_case: false,
+        replacements: &[],
+    },
+    KnownAlphabet {
+        name: "AlphaNumeric",
+        chars: "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+        fold_case: false,
+        replacements: &[],
+    },
+    KnownAlphabet {
+        name: "Base32Crockford",
+        chars: "0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+        fold_case: true,
+        replacements: &[
+            ('O', '0'),
+            ('o', '0'),
+            ('I', '1'),
+            ('i', '1'),
+            ('L', '1'),
+            ('l', '1'),
+        ],
+    },
+    KnownAlphabet {
+        name: "Hex",
+        chars: "0123456789abcdef",
+        fold_case: false,
+        replacements: &[],
+    },
+    KnownAlphabet {
+        name: "UpperSnake",
+        chars: "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_",
+        fold_case: false,
+        replacements: &[],
+    },
+    KnownAlphabet {
+        name: "Kebab",
+        chars: "abcdefghijklmnopqrstuvwxyz0123456789-",
+        fold_case: false,
+        replacements: &[],
+    },
+    KnownAlphabet {
+        name: "DottedPath",
+        chars: "abcdefghijklmnopqrstuvwxyz0123456789_.",
+        fold_case: false,
+        replacements: &[],
+    },
+];
+
+impl KnownAlphabet {
+    /// Looks up the [`KnownAlphabet`] referred to by the specified path, if any. Omitting the
+    /// path refers to `DefaultAlphabet`, and otherwise only paths that unambiguously name one
+    /// of the alphabets shipped with `smol-symbol`, i.e. `smol_symbol::Name` or
+    /// `::smol_symbol::Name`, are matched. Any other path (including a bare `Name`, which may
+    /// refer to a user-defined alphabet of the same name) is left to the const-eval checks.
+    fn find(path: Option<&TypePath>) -> Option<&'static KnownAlphabet> {
+        let name = match path {
+            None => "DefaultAlphabet".to_string(),
+            Some(path) => {
+                if path.qself.is_some() {
+                    return None;
+                }
+                let segments = &path.path.segments;
+                let prefix_ok = segments.len() == 2
+                    && segments[0].ident == "smol_symbol"
+                    && segments[0].arguments.is_none();
+                if !prefix_ok || !segments.last()?.arguments.is_none() {
+                    return None;
+                }
+                segments.last()?.ident.to_string()
+            }
+        };
+        KNOWN_ALPHABETS
+            .iter()
+            .find(|alphabet| alphabet.name == name)
+    }
+
+    /// Returns the `(alias, char)` pairs accepted in place of the characters of this
+    /// [`KnownAlphabet`], mirroring its `Normalization::aliases` in the main crate.
+    fn aliases(&self) -> Vec<(char, char)> {
+        let mut aliases = self.replacements.to_vec();
+        if self.fold_case {
+            push_case_aliases(&self.chars.chars().collect::<Vec<char>>(), &mut aliases);
+        }
+        aliases
+    }
+
+    /// Returns the canonical form of the specified symbol in this [`KnownAlphabet`], i.e. the
+    /// name it decodes to once parsed, by resolving any aliases to the characters they stand
+    /// for. Used to detect symbols that are written differently but encode the same value.
+    fn canonicalize(&self, symbol: &str) -> String {
+        let aliases = self.aliases();
+        symbol
+            .chars()
+            .map(|c| {
+                aliases
+                    .iter()
+                    .find(|(alias, _)| *alias == c)
+                    .map_or(c, |(_, target)| *target)
+            })
+            .collect()
+    }
+
+    /// Mirrors `CustomSymbol::MAX_SYMBOL_LEN` in the main crate, for a `SymbolRepr` with the
+    /// specified number of bits.
+    fn max_symbol_len(&self, bits: u32) -> usize {
+        max_symbol_len(self.chars.chars().count() as u128 + 1, bits)
+    }
+
+    /// Ensures the specified symbol is valid in this [`KnownAlphabet`], producing an error
+    /// with the specified [`Span`] describing the problem if it is not. The length of the
+    /// symbol is only checked if the number of bits in its `SymbolRepr` is known.
+    fn validate(&self, symbol: &str, span: Span, bits: Option<u32>) -> Result<()> {
+        self.check(symbol, bits)
+            .map_err(|(_, message)| Error::new(span, message))
+    }
+
+    /// Implements [`KnownAlphabet::validate`], returning the message describing the problem
+    /// along with the (0-based) index of the offending character, if there is one, so that
+    /// callers can point the error at that character.
+    fn check(
+        &self,
+        symbol: &str,
+        bits: Option<u32>,
+    ) -> std::result::Result<(), (Option<usize>, String)> {
+        let alphabet = self.chars.chars().collect::<Vec<char>>();
+        let aliases = self.aliases();
+        let max = bits.map(|bits| self.max_symbol_len(bits));
+        for (index, c) in symbol.chars().enumerate() {
+            if !alphabet.contains(&c) && !aliases.iter().any(|(alias, _)| *alias == c) {
+                let limit = match (max, bits) {
+                    (Some(max), Some(bits)) => format!(
+                        " and symbols of up to {max} characters (`CustomSymbol::MAX_SYMBOL_LEN` \
+                        for a {bits}-bit `SymbolRepr`)"
+                    ),
+                    _ => String::new(),
+                };
+                return Err((
+                    Some(index),
+                    format!(
+                        "invalid character {c:?} at position {index} in symbol `{symbol}`, \
+                        `{}` only allows the characters `{}`{limit}",
+                        self.name, self.chars
+                    ),
+                ));
+            }
+        }
+        let len = symbol.chars().count();
+        if len == 0 {
+            return Err((
+                None,
+                "symbols must be at least one character long".to_string(),
+            ));
+        }
+        let (Some(max), Some(bits)) = (max, bits) else {
+            return Ok(());
+        };
+        if len > max {
+            return Err((
+                None,
+                format!(
+                    "symbol `{symbol}` is {len} characters long, but `{}` only allows symbols \
+                    of up to {max} characters when backed by a {bits}-bit `SymbolRepr` \
+                    (`CustomSymbol::MAX_SYMBOL_LEN`)",
+                    self.name
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Determines the number of bits in the `SymbolRepr` referred to by the specified path, if it
+/// is one of the `SymbolRepr`s shipped with `smol-symbol`. Symbols default to a [`u128`], as
+/// that is the `Alphabet::Repr` of all [`KNOWN_ALPHABETS`].
+fn repr_bits(path: Option<&TypePath>) -> Option<u32> {
+    let Some(path) = path else {
+        return Some(128);
+    };
+    if path.qself.is_some() || !path.path.segments.last()?.arguments.is_none() {
+        return None;
+    }
+    match path.path.segments.last()?.ident.to_string().as_str() {
+        "u32" => Some(32),
+        "u64" => Some(64),
+        "u128" => Some(128),
+        "U256" => Some(256),
+        _ => None,
+    }
+}
+
+/// Mirrors the `max_symbol_len` function in the main crate, i.e. the largest number of
+/// base-`char_size` digits that can always be stored in `bits` bits.
+fn max_symbol_len(char_size: u128, bits: u32) -> usize {
+    if char_size < 2 {
+        return 0;
+    }
+    // `max` is `char_size^len - 1`, stored as little-endian 64-bit limbs
+    let mut max: Vec<u64> = Vec::new();
+    let mut len = 0;
+    loop {
+        let mut next = Vec::with_capacity(max.len() + 2);
+        let mut carry = char_size - 1;
+        for limb in &max {
+            let value = *limb as u128 * char_size + carry;
+            next.push(value as u64);
+            carry = value >> 64;
+        }
+        while carry != 0 {
+            next.push(carry as u64);
+            carry >>= 64;
+        }
+        let bit_len = next.len() as u32 * 64 - next.last().map_or(0, |limb| limb.leading_zeros());
+        if bit_len > bits {
+            return len;
+        }
+        max = next;
+        len += 1;
+    }
+}
+
+/// Generates an expression that evaluates to the symbol consisting of the specified
+/// characters at const-eval time, using the `Alphabet` at the specified path and, if
+/// specified, a non-default `SymbolRepr`.
+fn symbol_expr(alphabet_path: &TypePath, repr: Option<&TypePath>, symbol: &str) -> TokenStream2 {
+    let chars = symbol.chars();
+    match repr {
+        None => quote!(#alphabet_path::parse_chars_panic(&[#(#chars),*])),
+        Some(repr) => quote! {
+            match ::smol_symbol::CustomSymbol::<_, #alphabet_path, #repr>::try_from_chars(&[#(#chars),*]) {
+                Ok(symbol) => symbol,
+                Err(err) => panic!("{}", err.message()),
+            }
+        },
+    }
+}
+
+/// Generates `const` assertions that the symbol at each specified index differs from the
+/// symbols at the corresponding other indices, reporting the corresponding error message
+/// otherwise. Used for alphabets that aren't [`KNOWN_ALPHABETS`], whose normalization rules
+/// (and thus which differently written symbols collide) are only known during const-eval.
+/// The assertions are returned as statements, to be evaluated in a `const` block.
+fn distinct_symbols(
+    values: &[TokenStream2],
+    checks: &[(usize, Vec<usize>, String)],
+) -> TokenStream2 {
+    let checks = checks
+        .iter()
+        .filter(|(_, others, _)| !others.is_empty())
+        .collect::<Vec<_>>();
+    if checks.is_empty() {
+        return TokenStream2::new();
+    }
+    let len = values.len();
+    let asserts = checks.iter().map(|(index, others, message)| {
+        quote! {
+            assert!(
+                !::smol_symbol::__is_duplicate(&__SMOL_SYMBOL_WORDS, &[#(#others),*], #index),
+                #message
+            );
+        }
+    });
+    quote! {
+        const __SMOL_SYMBOL_WORDS: [[u64; 4]; #len] = [#((#values).__words()),*];
+        #(#asserts)*
+    }
+}
+
+/// Used to parse input to [`s!`]. The symbol itself can be specified either as an ident
+/// (including keywords and raw idents such as `r#type`, which yield `type`) or as a string
+/// literal, or as several of these joined by `+`, optionally followed by the path to an
+/// `Alphabet` and the path to a `SymbolRepr`.
 struct SymbolInput {
-    ident: Ident,
-    _comma: Option<Token![,]>,
-    #[parse_if(_comma.is_some())]
+    symbol: String,
+    /// The parts the symbol is made up of, used to point errors at the offending character.
+    parts: Vec<SymbolPart>,
+    /// The tokens making up the symbol, used to report errors spanning all of its parts.
+    tokens: TokenStream2,
     alphabet_path: Option<TypePath>,
+    repr: Option<TypePath>,
 }
 
-/// Generates a `Symbol` or `CustomSymbol` at const-eval time based on the provided ident and
-/// (optional) path to a custom `Alphabet`., e.g.:
+/// A single ident or string literal of a [`SymbolInput`].
+struct SymbolPart {
+    value: String,
+    span: Span,
+    /// The string literal this part was parsed from, if any.
+    literal: Option<LitStr>,
+}
+
+impl SymbolInput {
+    /// Returns the [`Span`] of the character at the specified (0-based) index of the symbol.
+    /// This is the span of just that character where the compiler supports it (i.e. for
+    /// string literals on nightly), and otherwise the span of the ident or string literal
+    /// containing it.
+    fn char_span(&self, index: usize) -> Span {
+        let mut start = 0;
+        for part in &self.parts {
+            let len = part.value.chars().count();
+            if index >= start + len {
+                start += len;
+                continue;
+            }
+            let Some(literal) = &part.literal else {
+                return part.span;
+            };
+            // only literals without escapes map characters directly onto source bytes
+            let token = literal.token();
+            if token.to_string() != format!("\"{}\"", part.value) {
+                return part.span;
+            }
+            let (offset, c) = part.value.char_indices().nth(index - start).unwrap();
+            return token
+                .subspan(1 + offset..1 + offset + c.len_utf8())
+                .unwrap_or(part.span);
+        }
+        self.parts
+            .last()
+            .map_or_else(Span::call_site, |part| part.span)
+    }
+}
+
+impl Parse for SymbolInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut tokens = TokenStream2::new();
+        tokens.extend([input.fork().parse::<TokenTree>()?]);
+        let mut parts = vec![parse_symbol_part(input)?];
+        while let Some(plus) = input.parse::<Option<Token![+]>>()? {
+            tokens.extend([quote!(#plus), input.fork().parse()?]);
+            parts.push(parse_symbol_part(input)?);
+        }
+        let (alphabet_path, repr) = parse_alphabet_and_repr(input)?;
+        Ok(SymbolInput {
+            symbol: parts.iter().map(|part| part.value.as_str()).collect(),
+            parts,
+            tokens,
+            alphabet_path,
+            repr,
+        })
+    }
+}
+
+/// Parses a single [`SymbolPart`], specified either as an ident (including keywords and raw
+/// idents) or as a string literal.
+fn parse_symbol_part(input: ParseStream) -> Result<SymbolPart> {
+    if input.peek(LitStr) {
+        let lit = input.parse::<LitStr>()?;
+        Ok(SymbolPart {
+            value: lit.value(),
+            span: lit.span(),
+            literal: Some(lit),
+        })
+    } else {
+        let (value, span) = parse_symbol(input)?;
+        Ok(SymbolPart {
+            value,
+            span,
+            literal: None,
+        })
+    }
+}
+
+/// Parses a single symbol, specified either as an ident (including keywords and raw idents)
+/// or as a string literal, returning its characters and span.
+fn parse_symbol(input: ParseStream) -> Result<(String, Span)> {
+    if input.peek(LitStr) {
+        let lit = input.parse::<LitStr>()?;
+        Ok((lit.value(), lit.span()))
+    } else {
+        let ident = Ident::parse_any(input)?;
+        Ok((ident.unraw().to_string(), ident.span()))
+    }
+}
+
+/// Parses the optional `, Alphabet` and `, SymbolRepr` paths that can follow the input to
+/// [`s!`] and [`sym_match!`].
+fn parse_alphabet_and_repr(input: ParseStream) -> Result<(Option<TypePath>, Option<TypePath>)> {
+    if input.parse::<Option<Token![,]>>()?.is_none() {
+        return Ok((None, None));
+    }
+    let alphabet_path = input.parse()?;
+    if input.parse::<Option<Token![,]>>()?.is_none() {
+        return Ok((Some(alphabet_path), None));
+    }
+    Ok((Some(alphabet_path), Some(input.parse()?)))
+}
+
+/// Generates a `Symbol` or `CustomSymbol` at const-eval time based on the provided ident or
+/// string literal and (optional) path to a custom `Alphabet`., e.g.:
 ///
 /// ```ignore
 /// let my_sym = s!(hello_world); // uses Symbol / DefaultAlphabet
 /// let my_custom_sym = s!(OtHeR, MyCustomAlphabet); // uses the custom alphabet `MyCustomAlphabet`
+/// let keyword = s!(r#type); // raw idents yield the keyword itself, i.e. `type`
+/// let digits = s!("123abc", MyCustomAlphabet); // string literals can contain any characters
+/// let compact = s!(hello, DefaultAlphabet, u64); // backed by a `u64` rather than a `u128`
+/// let joined = s!(user + "_" + created); // concatenated at compile time, i.e. `user_created`
 /// ```
 ///
-/// Your symbol ident should be constrained to a minimum of one character and should be no
-/// longer than the `MAX_SYMBOL_LEN` for your chosen alphabet (this is 25 for `DefaultAlphabet`).
+/// Your symbol should be constrained to a minimum of one character and should be no longer
+/// than the `MAX_SYMBOL_LEN` for your chosen alphabet (this is 26 for `DefaultAlphabet`).
+/// String literals are useful for symbols that aren't valid idents, such as those starting
+/// with a digit or containing characters like `-` that are permitted by a custom `Alphabet`.
+/// Any number of idents and string literals can be joined with `+`, in which case they are
+/// concatenated into a single symbol (which must itself be valid) at compile time.
+///
+/// The optional third argument specifies the `SymbolRepr` (`u32`, `u64`, `u128` or `U256`)
+/// backing the symbol, and defaults to the `Alphabet::Repr` of the alphabet. Wider types allow
+/// for longer symbols.
 ///
 /// At runtime, each unique`Symbol` is represented internally as a unique [`u128`] that encodes
 /// the bits of the symbol (5 bits per character when using `DefaultAlphabet`), and enough
@@ -36,47 +455,1088 @@ struct SymbolInput {
 /// type in the Crystal programming language, though it is strictly much more powerful, with
 /// the additional capability that `Symbol`s can be created and runtime in addition to
 /// compile-time, and can be directly sorted, hashed, etc., in lexically consistent way.
+///
+/// Symbols are always validated at compile time. When the alphabet is omitted, or is one of
+/// the alphabets shipped with `smol-symbol` referred to by its full path (e.g.
+/// `smol_symbol::Hex`), this is done by the macro itself, so invalid characters or symbols
+/// that are too long are reported as regular compile errors naming the offending character,
+/// its position and the maximum symbol length, and pointing at the offending character (or
+/// at least the ident or literal containing it). Symbols using any other alphabet path are
+/// validated during const-eval instead, with less precise errors. Note that this includes
+/// built-in alphabets that have been imported and are referred to by name alone (e.g.
+/// `s!(hello, DefaultAlphabet)` or `s!(beef, Hex)`), since the macro can't tell them apart from
+/// user-defined alphabets of the same name.
 #[proc_macro]
 pub fn s(tokens: TokenStream) -> TokenStream {
     let input = parse_macro_input!(tokens as SymbolInput);
-    let ident = input.ident.to_string();
-    let chars = ident.chars();
+    if let Some(alphabet) = KnownAlphabet::find(input.alphabet_path.as_ref()) {
+        let bits = repr_bits(input.repr.as_ref());
+        if let Err((index, message)) = alphabet.check(&input.symbol, bits) {
+            let err = match index {
+                Some(index) => Error::new(input.char_span(index), message),
+                None => Error::new_spanned(&input.tokens, message),
+            };
+            return err.to_compile_error().into();
+        }
+    }
     let alphabet_path = input
         .alphabet_path
         .unwrap_or_else(|| parse_quote!(::smol_symbol::DefaultAlphabet));
+    let symbol = symbol_expr(&alphabet_path, input.repr.as_ref(), &input.symbol);
     quote! {
-        #alphabet_path::parse_chars_panic(&[#(#chars),*])
+        const { #symbol }
     }
     .into()
 }
 
+/// A single arm of a [`sym_match!`] invocation.
+enum SymMatchArm {
+    /// One or more symbols (e.g. `foo | "bar" | r#type`), as `(symbol, span)` pairs.
+    Symbols(Vec<(String, Span)>, syn::Arm),
+    /// The fallback arm, either `_` or `name @ _`.
+    Fallback(Option<Ident>, syn::Arm),
+}
+
+impl SymMatchArm {
+    fn from_arm(arm: syn::Arm) -> Result<Self> {
+        match &arm.pat {
+            Pat::Wild(_) => Ok(SymMatchArm::Fallback(None, arm)),
+            Pat::Ident(PatIdent {
+                ident,
+                subpat: Some((_, subpat)),
+                by_ref: None,
+                mutability: None,
+                ..
+            }) if matches!(**subpat, Pat::Wild(_)) => {
+                Ok(SymMatchArm::Fallback(Some(ident.clone()), arm))
+            }
+            pat => {
+                let symbols = match pat {
+                    Pat::Or(or) => or
+                        .cases
+                        .iter()
+                        .map(symbol_from_pat)
+                        .collect::<Result<_>>()?,
+                    pat => vec![symbol_from_pat(pat)?],
+                };
+                Ok(SymMatchArm::Symbols(symbols, arm))
+            }
+        }
+    }
+}
+
+/// Interprets a single pattern in a [`sym_match!`] arm as a symbol.
+fn symbol_from_pat(pat: &Pat) -> Result<(String, Span)> {
+    match pat {
+        Pat::Ident(PatIdent {
+            ident,
+            by_ref: None,
+            mutability: None,
+            subpat: None,
+            ..
+        }) => Ok((ident.unraw().to_string(), ident.span())),
+        Pat::Lit(PatLit {
+            lit: Lit::Str(lit), ..
+        }) => Ok((lit.value(), lit.span())),
+        pat => Err(Error::new_spanned(
+            pat,
+            "expected a symbol (an ident or string literal), `_`, or `name @ _`",
+        )),
+    }
+}
+
+/// Used to parse input to [`sym_match!`].
+struct SymMatchInput {
+    expr: Expr,
+    alphabet_path: Option<TypePath>,
+    repr: Option<TypePath>,
+    arms: Vec<SymMatchArm>,
+}
+
+impl Parse for SymMatchInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let expr = Expr::parse_without_eager_brace(input)?;
+        let (alphabet_path, repr) = parse_alphabet_and_repr(input)?;
+        let content;
+        braced!(content in input);
+        let mut arms = Vec::new();
+        while !content.is_empty() {
+            arms.push(SymMatchArm::from_arm(content.call(syn::Arm::parse)?)?);
+        }
+        Ok(SymMatchInput {
+            expr,
+            alphabet_path,
+            repr,
+            arms,
+        })
+    }
+}
+
+/// Allows `Symbol`s / `CustomSymbol`s to be used as patterns in a `match`-like expression,
+/// which is not possible using [`s!`] directly since it expands to a (const) function call
+/// rather than a pattern:
+///
+/// ```ignore
+/// let action = sym_match!(sym {
+///     start | begin => 1,
+///     "stop" => 2,
+///     r#type if verbose => 3,
+///     _ => 0,
+/// });
+/// let action = sym_match!(sym, MyCustomAlphabet {
+///     Start => 1,
+///     other @ _ => panic!("unexpected symbol {other}"),
+/// });
+/// ```
+///
+/// Each arm consists of one or more symbols (idents, raw idents, or string literals, just
+/// like [`s!`]) separated by `|`, optionally followed by a guard. The symbols are encoded at
+/// compile time as constants, and the resulting `match` is performed directly on the backing
+/// integer, so this is just as efficient as matching on integer literals. Symbols that are
+/// already matched by a previous (unguarded) arm, or that appear more than once in the same
+/// arm, are reported as compile errors. This includes symbols that are written differently
+/// but are the same once normalized according to the `Alphabet::NORMALIZATION` rules.
+///
+/// Because symbols can't be matched exhaustively, a fallback arm is required. This can
+/// either be `_`, or `name @ _` to bind the unmatched symbol to `name`, and must come last
+/// (though guarded fallback arms may appear anywhere).
+///
+/// The optional `Alphabet` and `SymbolRepr` paths after the matched expression work the same
+/// way as they do for [`s!`], and default to `DefaultAlphabet` and its `Alphabet::Repr`.
+#[proc_macro]
+pub fn sym_match(tokens: TokenStream) -> TokenStream {
+    match sym_match_internal(parse_macro_input!(tokens as SymMatchInput)) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn sym_match_internal(input: SymMatchInput) -> Result<TokenStream2> {
+    let known_alphabet = KnownAlphabet::find(input.alphabet_path.as_ref());
+    let bits = repr_bits(input.repr.as_ref());
+    let alphabet_path = input
+        .alphabet_path
+        .unwrap_or_else(|| parse_quote!(::smol_symbol::DefaultAlphabet));
+    let repr = match &input.repr {
+        Some(repr) => quote!(#repr),
+        None => quote!(<#alphabet_path as ::smol_symbol::__AlphabetInfo>::__Repr),
+    };
+    let symbol = Ident::new("__symbol", Span::mixed_site());
+    let mut consts = Vec::new();
+    let mut arms = Vec::new();
+    let mut seen = HashSet::new();
+    let mut seen_indices = Vec::new();
+    let mut checks = Vec::new();
+    let mut fallback = None;
+    for arm in input.arms {
+        if fallback.is_some() {
+            let arm = match &arm {
+                SymMatchArm::Symbols(_, arm) | SymMatchArm::Fallback(_, arm) => arm,
+            };
+            return Err(Error::new_spanned(
+                &arm.pat,
+                "unreachable arm, the fallback arm must come last",
+            ));
+        }
+        match arm {
+            SymMatchArm::Symbols(symbols, arm) => {
+                let mut pats = Vec::new();
+                let mut names = Vec::new();
+                let mut indices = Vec::new();
+                for (name, span) in symbols {
+                    // symbols that are written differently can still normalize to the same
+                    // symbol, which is checked during const-eval for other alphabets
+                    let key = match known_alphabet {
+                        Some(alphabet) => {
+                            alphabet.validate(&name, span, bits)?;
+                            alphabet.canonicalize(&name)
+                        }
+                        None => name.clone(),
+                    };
+                    // symbols in guarded arms can legitimately be matched again later on
+                    let previous_arm =
+                        format!("symbol `{name}` is already matched by a previous arm");
+                    if seen.contains(&key) {
+                        return Err(Error::new(span, previous_arm));
+                    }
+                    let this_arm = format!("symbol `{name}` is already matched by this arm");
+                    if names.contains(&key) {
+                        return Err(Error::new(span, this_arm));
+                    }
+                    let index = consts.len();
+                    if known_alphabet.is_none() {
+                        checks.push((index, seen_indices.clone(), previous_arm));
+                        checks.push((index, indices.clone(), this_arm));
+                    }
+                    let const_ident = format_ident!("__SMOL_SYMBOL_ARM_{}", index);
+                    consts.push((
+                        const_ident.clone(),
+                        symbol_expr(&alphabet_path, input.repr.as_ref(), &name),
+                    ));
+                    names.push(key);
+                    indices.push(index);
+                    pats.push(const_ident);
+                }
+                if arm.guard.is_none() {
+                    seen.extend(names);
+                    seen_indices.extend(indices);
+                }
+                let guard = arm.guard.map(|(if_token, guard)| quote!(#if_token #guard));
+                let body = arm.body;
+                arms.push(quote!(#(#pats)|* #guard => #body));
+            }
+            SymMatchArm::Fallback(binding, arm) => {
+                // the binding is repeated in the guard and the body, and may be used by either
+                let binding = binding.map(|binding| {
+                    quote! {
+                        #[allow(unused_variables)]
+                        let #binding = #symbol;
+                    }
+                });
+                let guard = arm
+                    .guard
+                    .as_ref()
+                    .map(|(if_token, guard)| quote!(#if_token { #binding #guard }));
+                let body = arm.body;
+                let arm = quote!(_ #guard => { #binding #body });
+                match guard {
+                    Some(_) => arms.push(arm),
+                    None => fallback = Some(arm),
+                }
+            }
+        }
+    }
+    let Some(fallback) = fallback else {
+        return Err(Error::new(
+            Span::call_site(),
+            "`sym_match!` requires a fallback arm (`_` or `name @ _`), since symbols cannot be \
+            matched exhaustively",
+        ));
+    };
+    let expr = input.expr;
+    let raw = match consts.first() {
+        Some((_, first)) => {
+            quote!(::smol_symbol::CustomSymbol::__match_raw(#symbol, const { #first }))
+        }
+        None => quote!(::smol_symbol::CustomSymbol::to_raw(#symbol)),
+    };
+    let values = consts
+        .iter()
+        .map(|(_, value)| value.clone())
+        .collect::<Vec<_>>();
+    let distinct = distinct_symbols(&values, &checks);
+    let consts = consts.iter().map(
+        |(ident, value)| quote!(const #ident: #repr = ::smol_symbol::CustomSymbol::to_raw(#value);),
+    );
+    Ok(quote! {
+        {
+            #(#consts)*
+            const _: () = { #distinct };
+            let #symbol = #expr;
+            match #raw {
+                #(#arms,)*
+                #fallback
+            }
+        }
+    })
+}
+
+/// Converts a `CamelCase` variant name into `snake_case`, treating runs of capitals as a
+/// single word, e.g. both `HttpServer` and `HTTPServer` become `http_server`.
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<char>>();
+    let mut result = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_uppercase() {
+            result.push(c);
+            continue;
+        }
+        let boundary = match i.checked_sub(1).map(|prev| chars[prev]) {
+            Some(prev) if prev.is_uppercase() => chars.get(i + 1).is_some_and(|c| c.is_lowercase()),
+            Some(prev) => prev.is_lowercase() || prev.is_numeric(),
+            None => false,
+        };
+        if boundary {
+            result.push('_');
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
+/// Derives conversions between a unit-only enum and `Symbol` / `CustomSymbol`, mapping each
+/// variant to a symbol of its name converted to `snake_case`:
+///
+/// ```ignore
+/// #[derive(Symbolic)]
+/// enum Opcode {
+///     Start,                  // s!(start)
+///     StopAll,                // s!(stop_all)
+///     #[symbol(rename = "kill")]
+///     Terminate,              // s!(kill)
+/// }
+///
+/// #[derive(Symbolic)]
+/// #[symbol(alphabet = MyCustomAlphabet)]
+/// enum Color {
+///     Red,                    // s!(red, MyCustomAlphabet)
+/// }
+///
+/// assert_eq!(Opcode::StopAll.to_symbol(), s!(stop_all));
+/// assert_eq!(Opcode::from_symbol(s!(kill)), Some(Opcode::Terminate));
+/// let sym: Symbol = Opcode::Start.into();
+/// let op = Opcode::try_from(sym).unwrap();
+/// ```
+///
+/// The following are generated:
+/// - `SYMBOLS`, a const array of the symbols of each variant, in declaration order.
+/// - `const fn to_symbol(&self)`, returning the symbol of a variant.
+/// - `const fn from_symbol(symbol) -> Option<Self>`, the inverse of `to_symbol`.
+/// - `From<Enum>` for the symbol type, and `TryFrom<Symbol>` for the enum, which returns the
+///   unrecognized symbol as its error.
+///
+/// Variant names that cannot be represented in the chosen `Alphabet` (which defaults to
+/// `DefaultAlphabet`) result in a compile error, as do two variants sharing the same symbol
+/// (including symbols that only become the same once normalized, e.g. via `fold_case`).
+#[proc_macro_derive(Symbolic, attributes(symbol))]
+pub fn derive_symbolic(tokens: TokenStream) -> TokenStream {
+    match derive_symbolic_internal(parse_macro_input!(tokens as DeriveInput)) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn derive_symbolic_internal(input: DeriveInput) -> Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`Symbolic` can only be derived for enums",
+        ));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "`Symbolic` cannot be derived for generic enums",
+        ));
+    }
+    let mut alphabet_path = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("symbol"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("alphabet") {
+                return Err(meta.error("expected `alphabet = ...`"));
+            }
+            alphabet_path = Some(meta.value()?.parse::<TypePath>()?);
+            Ok(())
+        })?;
+    }
+    let known_alphabet = KnownAlphabet::find(alphabet_path.as_ref());
+    let alphabet_path =
+        alphabet_path.unwrap_or_else(|| parse_quote!(::smol_symbol::DefaultAlphabet));
+    let mut seen = HashSet::new();
+    let mut checks = Vec::new();
+    let mut variants = Vec::new();
+    let mut symbols = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "`Symbolic` can only be derived for enums whose variants have no fields",
+            ));
+        }
+        let mut rename = None;
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("symbol"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if !meta.path.is_ident("rename") {
+                    return Err(meta.error("expected `rename = \"...\"`"));
+                }
+                rename = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            })?;
+        }
+        let (name, span) = match rename {
+            Some(lit) => (lit.value(), lit.span()),
+            None => (
+                to_snake_case(&variant.ident.unraw().to_string()),
+                variant.ident.span(),
+            ),
+        };
+        let key = match known_alphabet {
+            Some(alphabet) => {
+                alphabet.validate(&name, span, repr_bits(None))?;
+                alphabet.canonicalize(&name)
+            }
+            None => name.clone(),
+        };
+        let message = format!("symbol `{name}` is already used by a previous variant");
+        if !seen.insert(key) {
+            return Err(Error::new(span, message));
+        }
+        if known_alphabet.is_none() {
+            checks.push((symbols.len(), (0..symbols.len()).collect(), message));
+        }
+        variants.push(&variant.ident);
+        symbols.push(symbol_expr(&alphabet_path, None, &name));
+    }
+    let distinct = distinct_symbols(&symbols, &checks);
+    let ident = &input.ident;
+    let len = variants.len();
+    let indices = (0..len).collect::<Vec<_>>();
+    let const_idents = indices
+        .iter()
+        .map(|i| format_ident!("__SMOL_SYMBOL_VARIANT_{}", i))
+        .collect::<Vec<_>>();
+    let symbol_ty = quote! {
+        ::smol_symbol::CustomSymbol<
+            { <#alphabet_path as ::smol_symbol::__AlphabetInfo>::__LEN },
+            #alphabet_path
+        >
+    };
+    let repr_ty = quote!(<#alphabet_path as ::smol_symbol::__AlphabetInfo>::__Repr);
+    Ok(quote! {
+        #[automatically_derived]
+        impl #ident {
+            /// The symbols corresponding to each variant, in declaration order.
+            pub const SYMBOLS: [#symbol_ty; #len] = [#(#symbols),*];
+
+            /// Returns the symbol corresponding to this variant.
+            pub const fn to_symbol(&self) -> #symbol_ty {
+                match *self {
+                    #(#ident::#variants => #ident::SYMBOLS[#indices],)*
+                }
+            }
+
+            /// Returns the variant corresponding to the specified symbol, if any.
+            pub const fn from_symbol(symbol: #symbol_ty) -> ::core::option::Option<Self> {
+                #(
+                    const #const_idents: #repr_ty =
+                        ::smol_symbol::CustomSymbol::to_raw(#ident::SYMBOLS[#indices]);
+                )*
+                match ::smol_symbol::CustomSymbol::to_raw(symbol) {
+                    #(#const_idents => ::core::option::Option::Some(#ident::#variants),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::convert::From<#ident> for #symbol_ty {
+            fn from(value: #ident) -> Self {
+                value.to_symbol()
+            }
+        }
+
+        #[automatically_derived]
+        impl ::core::convert::TryFrom<#symbol_ty> for #ident {
+            type Error = #symbol_ty;
+
+            fn try_from(value: #symbol_ty) -> ::core::result::Result<Self, Self::Error> {
+                #ident::from_symbol(value).ok_or(value)
+            }
+        }
+
+        // ensures symbols using custom alphabets are validated even if they are never used
+        const _: () = {
+            let _ = #ident::SYMBOLS;
+            #distinct
+        };
+    })
+}
+
+/// Determines whether the input to [`symbol_map!`] or [`symbol_set!`] begins with the path
+/// to an `Alphabet` (and optionally a `SymbolRepr`) followed by the braced entries, rather
+/// than with the entries themselves.
+fn has_alphabet_header(input: ParseStream) -> bool {
+    let fork = input.fork();
+    if fork.parse::<TypePath>().is_err() {
+        return false;
+    }
+    match fork.parse::<Option<Token![,]>>() {
+        Ok(Some(_)) => fork.parse::<TypePath>().is_ok() && fork.peek(token::Brace),
+        Ok(None) => fork.peek(token::Brace),
+        Err(_) => false,
+    }
+}
+
+/// Used to parse input to [`symbol_map!`] and [`symbol_set!`], where `T` is the type of each
+/// entry.
+struct SymbolTableInput<T> {
+    alphabet_path: Option<TypePath>,
+    repr: Option<TypePath>,
+    entries: Vec<T>,
+}
+
+impl<T> SymbolTableInput<T> {
+    fn parse_with(input: ParseStream, parse_entry: fn(ParseStream) -> Result<T>) -> Result<Self> {
+        let content;
+        let (alphabet_path, repr, body) = if has_alphabet_header(input) {
+            let (alphabet_path, repr) = (input.parse()?, parse_alphabet_and_repr(input)?.0);
+            braced!(content in input);
+            (Some(alphabet_path), repr, &content)
+        } else {
+            (None, None, input)
+        };
+        let entries = Punctuated::<T, Token![,]>::parse_terminated_with(body, parse_entry)?;
+        Ok(SymbolTableInput {
+            alphabet_path,
+            repr,
+            entries: entries.into_iter().collect(),
+        })
+    }
+}
+
+impl Parse for SymbolTableInput<((String, Span), Expr)> {
+    fn parse(input: ParseStream) -> Result<Self> {
+        SymbolTableInput::parse_with(input, |input| {
+            let key = parse_symbol(input)?;
+            input.parse::<Token![=>]>()?;
+            Ok((key, input.parse()?))
+        })
+    }
+}
+
+impl Parse for SymbolTableInput<(String, Span)> {
+    fn parse(input: ParseStream) -> Result<Self> {
+        SymbolTableInput::parse_with(input, parse_symbol)
+    }
+}
+
+/// Generates a `SymbolMap` with the specified keys, using the specified expression (which
+/// must evaluate to a `&'static` slice in declaration order) for its values.
+fn symbol_table(
+    alphabet_path: Option<TypePath>,
+    repr: Option<TypePath>,
+    keys: &[(String, Span)],
+    values: TokenStream2,
+) -> Result<TokenStream2> {
+    let known_alphabet = KnownAlphabet::find(alphabet_path.as_ref());
+    let bits = repr_bits(repr.as_ref());
+    let alphabet_path =
+        alphabet_path.unwrap_or_else(|| parse_quote!(::smol_symbol::DefaultAlphabet));
+    let mut seen = HashSet::new();
+    let mut symbols = Vec::new();
+    let mut checks = Vec::new();
+    for (name, span) in keys {
+        let key = match known_alphabet {
+            Some(alphabet) => {
+                alphabet.validate(name, *span, bits)?;
+                alphabet.canonicalize(name)
+            }
+            None => name.clone(),
+        };
+        let message = format!("symbol `{name}` is already used by a previous entry");
+        if !seen.insert(key) {
+            return Err(Error::new(*span, message));
+        }
+        if known_alphabet.is_none() {
+            checks.push((symbols.len(), (0..symbols.len()).collect(), message));
+        }
+        symbols.push(symbol_expr(&alphabet_path, repr.as_ref(), name));
+    }
+    let distinct = distinct_symbols(&symbols, &checks);
+    let len = symbols.len();
+    // an average of 5 keys per bucket keeps the displacement search fast while keeping the
+    // table small
+    let buckets = len.div_ceil(5);
+    let indices = 0..len;
+    let repr = repr.map(|repr| quote!(, #repr));
+    Ok(quote! {
+        {
+            const __SMOL_SYMBOL_KEYS: [
+                ::smol_symbol::CustomSymbol<
+                    { <#alphabet_path as ::smol_symbol::__AlphabetInfo>::__LEN },
+                    #alphabet_path
+                    #repr
+                >;
+                #len
+            ] = [#(#symbols),*];
+            // duplicates are checked first, since they would prevent finding a perfect hash
+            const __SMOL_SYMBOL_INDEX: ::smol_symbol::__PerfectHash<#len, #buckets> = {
+                #distinct
+                ::smol_symbol::__PerfectHash::new(&[#(__SMOL_SYMBOL_KEYS[#indices].__words()),*])
+            };
+            ::smol_symbol::SymbolMap::__new(
+                &__SMOL_SYMBOL_KEYS,
+                #values,
+                __SMOL_SYMBOL_INDEX.seed,
+                &__SMOL_SYMBOL_INDEX.displacements,
+                &__SMOL_SYMBOL_INDEX.slots,
+            )
+        }
+    })
+}
+
+/// Generates a `SymbolMap`, an immutable map from symbols to values that uses a perfect hash
+/// function found at compile time, e.g.:
+///
+/// ```ignore
+/// static OPCODES: SymbolMap<u8> = symbol_map! {
+///     push => 0x01,
+///     pop => 0x02,
+///     "jump-if" => 0x03,
+///     r#return => 0x04,
+/// };
+/// static CUSTOM: SymbolMap<u8, CustomSymbol<26, MyCustomAlphabet>> = symbol_map!(MyCustomAlphabet {
+///     Push => 0x01,
+/// });
+/// ```
+///
+/// Keys are specified just like the symbols passed to [`s!`], and the optional `Alphabet` and
+/// `SymbolRepr` paths preceding the braced entries work the same way as they do for [`s!`].
+/// Keys that appear more than once (including keys that only become the same once
+/// normalized) are reported as compile errors.
+///
+/// Since the keys and values are borrowed for `'static`, the resulting map is intended to be
+/// stored in a `static` or `const` item (values that are constant expressions also work in
+/// other contexts, thanks to constant promotion).
+#[proc_macro]
+pub fn symbol_map(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as SymbolTableInput<((String, Span), Expr)>);
+    let (keys, values): (Vec<_>, Vec<_>) = input.entries.into_iter().unzip();
+    match symbol_table(
+        input.alphabet_path,
+        input.repr,
+        &keys,
+        quote!(&[#(#values),*]),
+    ) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Generates a `SymbolSet`, the set counterpart of the `SymbolMap` generated by
+/// [`symbol_map!`], e.g.:
+///
+/// ```ignore
+/// static KEYWORDS: SymbolSet = symbol_set! { r#if, r#else, r#while, "do-while" };
+/// static CUSTOM: SymbolSet<CustomSymbol<26, MyCustomAlphabet>> = symbol_set!(MyCustomAlphabet {
+///     If, Else,
+/// });
+/// ```
+#[proc_macro]
+pub fn symbol_set(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as SymbolTableInput<(String, Span)>);
+    let len = input.entries.len();
+    match symbol_table(
+        input.alphabet_path,
+        input.repr,
+        &input.entries,
+        quote!(&[(); #len]),
+    ) {
+        Ok(map) => quote!(::smol_symbol::SymbolSet::__new(#map)).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Adds an alias for the other case of each character in the specified alphabet to the
+/// specified `(alias, char)` pairs, unless that character is part of the alphabet itself or
+/// is already an alias. This implements the `fold_case` normalization rule.
+fn push_case_aliases(alphabet: &[char], aliases: &mut Vec<(char, char)>) {
+    for &c in alphabet {
+        // only characters with single-char case mappings can be folded
+        let (mut lower, mut upper) = (c.to_lowercase(), c.to_uppercase());
+        let (Some(lower), None, Some(upper), None) =
+            (lower.next(), lower.next(), upper.next(), upper.next())
+        else {
+            continue;
+        };
+        for other in [lower, upper] {
+            if other != c
+                && !alphabet.contains(&other)
+                && !aliases.iter().any(|(alias, _)| *alias == other)
+            {
+                aliases.push((other, c));
+            }
+        }
+    }
+}
+
 /// Used to parse input to [`custom_alphabet`].
-#[derive(Parse)]
 struct CustomAlphabetInput {
+    attrs: Vec<Attribute>,
     name: Ident,
-    _comma: Token![,],
-    alphabet: Ident,
+    alphabet: Vec<(char, Span)>,
+    alphabet_span: Span,
+    repr: Option<TypePath>,
+    normalize: Option<NormalizeInput>,
+    dedup: bool,
+}
+
+impl Parse for CustomAlphabetInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let (alphabet, alphabet_span) = parse_alphabet_chars(input)?;
+        let mut repr = None;
+        let mut normalize = None;
+        let mut dedup = false;
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            if input.peek(Ident)
+                && !input.peek2(Token![::])
+                && input.fork().parse::<Ident>()? == "dedup"
+            {
+                let ident = input.parse::<Ident>()?;
+                if dedup {
+                    return Err(Error::new(
+                        ident.span(),
+                        "`dedup` can only be specified once",
+                    ));
+                }
+                dedup = true;
+            } else if input.peek(Ident)
+                && input.peek2(token::Paren)
+                && input.fork().parse::<Ident>()? == "normalize"
+            {
+                if normalize.is_some() {
+                    return Err(input.error("`normalize(...)` can only be specified once"));
+                }
+                normalize = Some(input.parse()?);
+            } else if repr.is_none() && normalize.is_none() {
+                repr = Some(input.parse()?);
+            } else {
+                return Err(input.error("expected `normalize(...)` or `dedup`"));
+            }
+        }
+        if !input.is_empty() {
+            return Err(input.error("expected `,`"));
+        }
+        Ok(CustomAlphabetInput {
+            attrs,
+            name,
+            alphabet,
+            alphabet_span,
+            repr,
+            normalize,
+            dedup,
+        })
+    }
+}
+
+/// Parses the characters of an alphabet passed to [`custom_alphabet`], either as a single
+/// ident consisting of those characters, or as a bracketed, comma-separated list of `char`
+/// literals, inclusive `char` ranges (e.g. `'a'..='z'`) and string literals, e.g.
+/// `['a'..='z', '0'..='9', '-', "./"]`. Each character is returned along with the span it
+/// came from, followed by the span of the alphabet as a whole.
+fn parse_alphabet_chars(input: ParseStream) -> Result<(Vec<(char, Span)>, Span)> {
+    if !input.peek(token::Bracket) {
+        let ident = input.parse::<Ident>()?;
+        let chars = ident
+            .to_string()
+            .chars()
+            .map(|c| (c, ident.span()))
+            .collect();
+        return Ok((chars, ident.span()));
+    }
+    let content;
+    let brackets = bracketed!(content in input);
+    let mut alphabet = Vec::new();
+    while !content.is_empty() {
+        if content.peek(LitStr) {
+            let lit = content.parse::<LitStr>()?;
+            alphabet.extend(lit.value().chars().map(|c| (c, lit.span())));
+        } else {
+            let start = content.parse::<LitChar>()?;
+            if content.peek(Token![..=]) {
+                content.parse::<Token![..=]>()?;
+                let end = content.parse::<LitChar>()?;
+                if end.value() < start.value() {
+                    return Err(Error::new(
+                        end.span(),
+                        format!("the range {:?}..={:?} is empty", start.value(), end.value()),
+                    ));
+                }
+                let span = start.span().join(end.span()).unwrap_or(start.span());
+                alphabet.extend((start.value()..=end.value()).map(|c| (c, span)));
+            } else if content.peek(Token![..]) {
+                return Err(content.error("only inclusive ranges (`..=`) are supported"));
+            } else {
+                alphabet.push((start.value(), start.span()));
+            }
+        }
+        if content.parse::<Option<Token![,]>>()?.is_none() {
+            break;
+        }
+    }
+    if !content.is_empty() {
+        return Err(content.error("expected `,`"));
+    }
+    Ok((alphabet, brackets.span.join()))
+}
+
+/// Ensures the specified alphabet (as returned by [`parse_alphabet_chars`]) contains at least
+/// one character, and contains no duplicate characters (unless `dedup` is specified, in which
+/// case only the first occurrence of each character is kept). Alphabets can never be too
+/// large for a single character to fit in a `SymbolRepr`, since there are far fewer than
+/// `2^32` distinct [`char`]s.
+fn validate_alphabet(chars: Vec<(char, Span)>, span: Span, dedup: bool) -> Result<Vec<char>> {
+    let mut alphabet = Vec::with_capacity(chars.len());
+    for (c, c_span) in chars {
+        if !alphabet.contains(&c) {
+            alphabet.push(c);
+        } else if !dedup {
+            return Err(Error::new(
+                c_span,
+                format!(
+                    "duplicate character {c:?} in alphabet, which would make it unreachable \
+                    (specify the `dedup` option to only keep the first occurrence of each \
+                    character)"
+                ),
+            ));
+        }
+    }
+    if alphabet.is_empty() {
+        return Err(Error::new(
+            span,
+            "alphabets must contain at least one character",
+        ));
+    }
+    Ok(alphabet)
+}
+
+/// Generates `__Contains` impls between the specified alphabet and each of the
+/// [`KNOWN_ALPHABETS`] that it contains or is contained in, as long as the maximum symbol
+/// lengths allow it, which enable infallible `From` conversions between their symbols.
+/// Nothing is generated if the number of bits in the `SymbolRepr` of the alphabet is unknown.
+///
+/// When defining the [`KNOWN_ALPHABETS`] themselves (i.e. within the `smol_symbol` crate),
+/// the alphabet being defined is skipped, since it trivially contains itself. User-defined
+/// alphabets that merely share its name are distinct types, and are not skipped.
+fn alphabet_conversions(
+    name: &Ident,
+    alphabet: &[char],
+    bits: Option<u32>,
+    crate_path: &TokenStream2,
+) -> TokenStream2 {
+    let Some(bits) = bits else {
+        return TokenStream2::new();
+    };
+    let alphabet_len = alphabet.len();
+    let max = max_symbol_len(alphabet_len as u128 + 1, bits);
+    let defining_known =
+        std::env::var("CARGO_CRATE_NAME").is_ok_and(|krate| krate == "smol_symbol");
+    let mut impls = TokenStream2::new();
+    for known in KNOWN_ALPHABETS
+        .iter()
+        .filter(|known| !defining_known || *name != known.name)
+    {
+        let known_chars = known.chars.chars().collect::<Vec<char>>();
+        let known_max = known.max_symbol_len(128);
+        let known_name = format_ident!("{}", known.name);
+        let known_path = quote!(#crate_path::#known_name);
+        if known_max <= max && known_chars.iter().all(|c| alphabet.contains(c)) {
+            impls.extend(quote! {
+                impl #crate_path::__Contains<{ #known_path::LEN }, #known_path> for #name {}
+            });
+        }
+        if max <= known_max && alphabet.iter().all(|c| known_chars.contains(c)) {
+            impls.extend(quote! {
+                impl #crate_path::__Contains<#alphabet_len, #name> for #known_path {}
+            });
+        }
+    }
+    impls
+}
+
+/// Used to parse the `normalize(...)` option of [`custom_alphabet`], a comma-separated list
+/// of `fold_case`, `trim`, and `'a' | 'b' => 'c'` replacement rules.
+struct NormalizeInput {
+    span: Span,
+    fold_case: bool,
+    trim: bool,
+    replacements: Vec<(LitChar, LitChar)>,
+}
+
+impl Parse for NormalizeInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let span = input.parse::<Ident>()?.span();
+        let content;
+        parenthesized!(content in input);
+        let mut normalize = NormalizeInput {
+            span,
+            fold_case: false,
+            trim: false,
+            replacements: Vec::new(),
+        };
+        while !content.is_empty() {
+            if content.peek(LitChar) {
+                let mut from = vec![content.parse::<LitChar>()?];
+                while content.parse::<Option<Token![|]>>()?.is_some() {
+                    from.push(content.parse()?);
+                }
+                content.parse::<Token![=>]>()?;
+                let to = content.parse::<LitChar>()?;
+                normalize
+                    .replacements
+                    .extend(from.into_iter().map(|from| (from, to.clone())));
+            } else {
+                let rule = content.parse::<Ident>()?;
+                let flag =
+                    match rule.to_string().as_str() {
+                        "fold_case" => &mut normalize.fold_case,
+                        "trim" => &mut normalize.trim,
+                        _ => return Err(Error::new(
+                            rule.span(),
+                            "expected `fold_case`, `trim`, or a replacement such as `'-' => '_'`",
+                        )),
+                    };
+                if *flag {
+                    return Err(Error::new(
+                        rule.span(),
+                        format!("`{rule}` is already specified"),
+                    ));
+                }
+                *flag = true;
+            }
+            if content.parse::<Option<Token![,]>>()?.is_none() {
+                break;
+            }
+        }
+        if !content.is_empty() {
+            return Err(content.error("expected `,`"));
+        }
+        Ok(normalize)
+    }
+}
+
+impl NormalizeInput {
+    /// Resolves the case folding and replacement rules into `(alias, char)` pairs, ensuring
+    /// that replacements are unambiguous and target characters in the specified alphabet.
+    fn aliases(&self, alphabet: &[char]) -> Result<Vec<(char, char)>> {
+        let mut aliases: Vec<(char, char)> = Vec::new();
+        for (from, to) in &self.replacements {
+            if alphabet.contains(&from.value()) {
+                return Err(Error::new(
+                    from.span(),
+                    format!(
+                        "{:?} is already in the alphabet, so it can't be replaced",
+                        from.value()
+                    ),
+                ));
+            }
+            if !alphabet.contains(&to.value()) {
+                return Err(Error::new(
+                    to.span(),
+                    format!("{:?} is not in the alphabet", to.value()),
+                ));
+            }
+            if aliases.iter().any(|(alias, _)| *alias == from.value()) {
+                return Err(Error::new(
+                    from.span(),
+                    format!("{:?} is already replaced by a previous rule", from.value()),
+                ));
+            }
+            aliases.push((from.value(), to.value()));
+        }
+        if self.fold_case {
+            push_case_aliases(alphabet, &mut aliases);
+            if aliases.len() == self.replacements.len() {
+                return Err(Error::new(
+                    self.span,
+                    "`fold_case` has no effect, since the alphabet has no characters whose \
+                    other case is missing from it",
+                ));
+            }
+        }
+        Ok(aliases)
+    }
 }
 
 /// Allows you to define a custom alphabet for use with `CustomSymbol` and the [`s!`] macro.
-/// The macro takes two idents separated by a comma as input. The first ident should be the
-/// name of the alphabet you would like to create, and the second ident should contain all of
-/// the characters you would like to use in your alphabet (symbols must be comprised only of
-/// characters that are valid in an
-/// [ident](https://doc.rust-lang.org/reference/identifiers.html).
+/// The macro takes the name of the alphabet you would like to create, followed by the
+/// characters you would like to use in your alphabet. These can either be specified as a
+/// single ident containing all of the characters (which must therefore be valid in an
+/// [ident](https://doc.rust-lang.org/reference/identifiers.html)), or as a bracketed list of
+/// `char` literals, inclusive `char` ranges and string literals, which can contain any
+/// characters.
+///
+/// Either way, alphabets must contain at least one character, and may not contain any
+/// character more than once (since all but the first occurrence would be unreachable), unless
+/// the `dedup` option is specified, in which case only the first occurrence of each character
+/// is kept:
+///
+/// ```ignore
+/// custom_alphabet!(Letters, hello_world, dedup); // equivalent to `helo_wrd`
+/// ```
 ///
-/// For example, this would define `MyAlphabet` to consist of uppercase A-Z, lowercase a-z, and
-/// digits, and would have a resulting `MAX_SYMBOL_LEN` of 21 characters long:
+/// For example, these would both define an alphabet consisting of lowercase a-z, uppercase
+/// A-Z, and digits, with a resulting `MAX_SYMBOL_LEN` of 21 characters long:
 ///
 /// ```ignore
 /// custom_alphabet!(MyAlphabet, abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789);
+/// custom_alphabet!(MyRangeAlphabet, ['a'..='z', 'A'..='Z', '0'..='9']);
 ///
 /// let my_sym = s!(SoMeThInG33, MyAlphabet);
 /// ```
 ///
+/// The bracketed form also allows for characters that can't appear in an ident, which can
+/// then be used in symbols via string literals:
+///
+/// ```ignore
+/// custom_alphabet!(Path, ['a'..='z', '0'..='9', '-', '.', '/']);
+///
+/// let path_sym = s!("usr/lib/libc.so.6", Path);
+/// ```
+///
+/// Attributes such as doc comments can be placed before the name of the alphabet, and are
+/// applied to the generated struct.
+///
+/// Several ready-made alphabets are also provided alongside `DefaultAlphabet`, namely
+/// `AlphaNumeric`, `Base32Crockford`, `Hex`, `UpperSnake`, `Kebab` and `DottedPath`.
+///
 /// It is worth noting that in general, the longer an alphabet is, the lower the
-/// `MAX_SYMBOL_LEN` bound will be for that alphabet, since a [`u128`] is always used as the
-/// backing for `CustomSymbol`.
+/// `MAX_SYMBOL_LEN` bound will be for that alphabet, since a fixed-size integer is used as the
+/// backing for `CustomSymbol`. By default this is a [`u128`], but a different `SymbolRepr`
+/// (`u32`, `u64`, `u128` or `U256`) can be chosen via an optional third argument, which
+/// becomes the `Alphabet::Repr` of the alphabet:
+///
+/// ```ignore
+/// custom_alphabet!(Compact, abcdefghijklmnopqrstuvwxyz_, u64);
+///
+/// let compact_sym = s!(thirteen_char, Compact); // a `CustomSymbol<27, Compact, u64>`
+/// ```
+///
+/// Symbols can be converted between any two alphabets using `CustomSymbol::convert`, which
+/// fails if the characters or length of the symbol don't permit it. In addition, whenever
+/// one of the alphabets shipped with `smol-symbol` is a subset of the new alphabet (or vice
+/// versa) and its symbols can never be too long for the other, `From` impls are provided
+/// for converting in that direction infallibly:
+///
+/// ```ignore
+/// custom_alphabet!(Wide, ['a'..='z', '_', '0'..='9', 'A'..='Z'], U256);
+///
+/// let wide: CustomSymbol<63, Wide> = s!(hello_world).into();
+/// ```
+///
+/// Finally, a `normalize(...)` option can be specified to accept input that doesn't exactly
+/// match the alphabet, which becomes the `Alphabet::NORMALIZATION` of the alphabet. This is
+/// a comma-separated list of any of the following rules:
+/// - `fold_case`, which accepts the other case of each character in the alphabet (as long
+///   as that isn't part of the alphabet itself).
+/// - `trim`, which ignores leading and trailing ASCII whitespace.
+/// - Replacements such as `'-' | ' ' => '_'`, which accept each character on the left in
+///   place of the character of the alphabet on the right.
+///
+/// Normalization is applied by `TryFrom<&str>`, `FromStr`, `CustomSymbol::try_from_str` and
+/// [`s!`] alike, and symbols always decode to the characters of the alphabet itself.
+/// Alphabets without a `normalize(...)` option are strict, only accepting the exact
+/// characters of the alphabet, and the rules of a normalizing alphabet can be bypassed using
+/// `CustomSymbol::try_from_str_strict`:
+///
+/// ```ignore
+/// custom_alphabet!(Cli, abcdefghijklmnopqrstuvwxyz_, normalize(fold_case, '-' | ' ' => '_', trim));
+///
+/// assert_eq!(s!("Hello-World", Cli), s!(hello_world, Cli));
+/// assert_eq!(" hello world ".parse::<CustomSymbol<27, Cli>>(), Ok(s!(hello_world, Cli)));
+/// assert!(Cli::parse_str_strict("Hello-World").is_err());
+/// ```
 #[proc_macro]
 pub fn custom_alphabet(tokens: TokenStream) -> TokenStream {
     let crate_path = match std::env::var("CARGO_PKG_NAME") {
@@ -87,36 +1547,85 @@ pub fn custom_alphabet(tokens: TokenStream) -> TokenStream {
         _ => quote!(::smol_symbol),
     };
     let input = parse_macro_input!(tokens as CustomAlphabetInput);
+    let attrs = input.attrs;
     let name = input.name;
-    let alphabet = input.alphabet.to_string().chars().collect::<Vec<char>>();
+    let bits = repr_bits(input.repr.as_ref());
+    let alphabet = match validate_alphabet(input.alphabet, input.alphabet_span, input.dedup) {
+        Ok(alphabet) => alphabet,
+        Err(err) => return err.to_compile_error().into(),
+    };
     let alphabet_len = alphabet.len();
-    let alphabet_map_u128 = alphabet.iter().enumerate().map(|(i, c)| {
-        let i = i + 1;
-        let i = i as u128;
-        quote!(#c => #i)
-    });
+    let [name_buffer_32, name_buffer_64, name_buffer_128, name_buffer_256] =
+        [32, 64, 128, 256].map(|bits| name_buffer_len(&alphabet, bits));
+    let conversions = alphabet_conversions(&name, &alphabet, bits, &crate_path);
+    let repr = match input.repr {
+        Some(repr) => quote!(#repr),
+        None => quote!(u128),
+    };
+    let aliases = match input
+        .normalize
+        .as_ref()
+        .map(|normalize| normalize.aliases(&alphabet))
+    {
+        Some(Ok(aliases)) => aliases,
+        Some(Err(err)) => return err.to_compile_error().into(),
+        None => Vec::new(),
+    };
+    let index_of = |c: char| alphabet.iter().position(|other| *other == c).unwrap() as u128 + 1;
+    let alphabet_map_u128 = alphabet
+        .iter()
+        .map(|c| (*c, index_of(*c)))
+        .chain(aliases.iter().map(|(alias, c)| (*alias, index_of(*c))))
+        .map(|(c, i)| quote!(#c => #i));
     let alphabet_map_u128_clone = alphabet_map_u128.clone();
+    let normalization = input.normalize.map(|normalize| {
+        let trim = normalize.trim;
+        let (alias_chars, alias_targets): (Vec<_>, Vec<_>) = aliases.iter().copied().unzip();
+        quote! {
+            const NORMALIZATION: #crate_path::Normalization = #crate_path::Normalization {
+                trim: #trim,
+                aliases: &[#((#alias_chars, #alias_targets)),*],
+            };
+        }
+    });
     quote! {
+        #(#attrs)*
         #[derive(Copy, Clone, PartialEq, Eq)]
         pub struct #name;
 
         impl #crate_path::Alphabet<#alphabet_len> for #name {
             const ALPHABET: [char; #alphabet_len] = [#(#alphabet),*];
 
+            type Repr = #repr;
+
+            type NameBuffer32 = [u8; #name_buffer_32];
+            type NameBuffer64 = [u8; #name_buffer_64];
+            type NameBuffer128 = [u8; #name_buffer_128];
+            type NameBuffer256 = [u8; #name_buffer_256];
+
+            #normalization
+
             fn invert_char(c: char) -> core::result::Result<u128, #crate_path::SymbolParsingError> {
                 let i = match c {
                     #(#alphabet_map_u128),*,
-                    _ => return Err(#crate_path::SymbolParsingError),
+                    _ => return Err(#crate_path::SymbolParsingError::InvalidChar { ch: c, index: 0 }),
                 };
                 Ok(i as u128)
             }
         }
 
+        impl #crate_path::__AlphabetInfo for #name {
+            const __LEN: usize = #alphabet_len;
+            type __Repr = #repr;
+        }
+
+        #conversions
+
         impl #name {
             pub const fn invert_char(c: char) -> core::result::Result<u128, #crate_path::SymbolParsingError> {
                 let i = match c {
                     #(#alphabet_map_u128_clone),*,
-                    _ => return Err(#crate_path::SymbolParsingError),
+                    _ => return Err(#crate_path::SymbolParsingError::InvalidChar { ch: c, index: 0 }),
                 };
                 Ok(i as u128)
             }
@@ -125,31 +1634,77 @@ pub fn custom_alphabet(tokens: TokenStream) -> TokenStream {
                 #crate_path::CustomSymbol<#alphabet_len, #name>,
                 #crate_path::SymbolParsingError
             > {
-                let mut i = chars.len() - 1;
-                let mut data: u128 = 0;
-                loop {
-                    let c = chars[i];
-                    let inverted = Self::invert_char(c);
-                    data *= #name::LEN_U218 + 1;
-                    data += match inverted {
-                        Ok(val) => val,
-                        Err(err) => return Err(err),
-                    };
-                    if i == 0 {
-                        break;
-                    }
-                    i -= 1;
+                #crate_path::CustomSymbol::<#alphabet_len, #name>::try_from_chars(chars)
+            }
+
+            pub const fn parse_str(value: &str) -> core::result::Result<
+                #crate_path::CustomSymbol<#alphabet_len, #name>,
+                #crate_path::SymbolParsingError
+            > {
+                #crate_path::CustomSymbol::<#alphabet_len, #name>::try_from_str(value)
+            }
+
+            pub const fn parse_str_strict(value: &str) -> core::result::Result<
+                #crate_path::CustomSymbol<#alphabet_len, #name>,
+                #crate_path::SymbolParsingError
+            > {
+                #crate_path::CustomSymbol::<#alphabet_len, #name>::try_from_str_strict(value)
+            }
+
+            pub const fn parse_str_panic(value: &str) -> #crate_path::CustomSymbol<#alphabet_len, #name> {
+                match Self::parse_str(value) {
+                    Ok(sym) => sym,
+                    Err(err) => panic!("{}", err.message()),
                 }
-                Ok(#crate_path::CustomSymbol::from_raw(data))
             }
 
             pub const fn parse_chars_panic(chars: &[char]) -> #crate_path::CustomSymbol<#alphabet_len, #name> {
                 match Self::parse_chars(chars) {
                     Ok(sym) => sym,
-                    Err(err) => panic!("{}", #crate_path::PARSING_ERROR_MSG),
+                    Err(err) => panic!("{}", err.message()),
                 }
             }
         }
     }
     .into()
 }
+
+/// Determines how many bytes are needed to hold the UTF-8 encoding of the longest symbol that
+/// could be decoded from a `SymbolRepr` of the specified number of bits using the specified
+/// alphabet, i.e. the number of base-`(N + 1)` digits in such an integer times the length of
+/// the longest character in the alphabet. This is one more digit than `MAX_SYMBOL_LEN`, since
+/// `from_raw_unchecked` can produce values that don't correspond to a valid symbol.
+fn name_buffer_len(alphabet: &[char], bits: u32) -> usize {
+    let digits = max_symbol_len(alphabet.len() as u128 + 1, bits) + 1;
+    let max_char_len = alphabet.iter().map(|c| c.len_utf8()).max().unwrap_or(0);
+    digits * max_char_len
+}
+
+/// Used internally by `smol-symbol` to assert at compile time that each of the [`Alphabet`]s
+/// it ships with matches the characters and normalization rules recorded for it in
+/// [`KNOWN_ALPHABETS`], which are used to validate symbols at macro expansion time.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __check_known_alphabets(tokens: TokenStream) -> TokenStream {
+    parse_macro_input!(tokens as syn::parse::Nothing);
+    let checks = KNOWN_ALPHABETS.iter().map(|known| {
+        let name = format_ident!("{}", known.name);
+        let chars = known.chars.chars();
+        let (alias_chars, alias_targets): (Vec<char>, Vec<char>) =
+            known.aliases().into_iter().unzip();
+        let message = format!(
+            "`{}` is out of sync with its `KNOWN_ALPHABETS` entry in `smol-symbol-macros`",
+            known.name
+        );
+        quote! {
+            const _: () = assert!(
+                crate::matches_known_alphabet::<{ crate::#name::LEN }, crate::#name>(
+                    &[#(#chars),*],
+                    &[#((#alias_chars, #alias_targets)),*],
+                ),
+                #message
+            );
+        }
+    });
+    quote!(#(#checks)*).into()
+}
@@ -0,0 +1,187 @@
+//! Contains [`DynSymbol`], a symbol whose [`Alphabet`] is tracked at runtime rather than in
+//! its type, along with the [`DynAlphabet`] descriptors used to do so.
+
+use super::*;
+use core::any::{type_name, TypeId};
+
+/// A runtime descriptor of an [`Alphabet`], as used by [`DynSymbol`]. Each [`Alphabet`] has
+/// exactly one [`DynAlphabet`], which can be obtained via [`DynAlphabet::of`].
+pub struct DynAlphabet {
+    chars: &'static [char],
+    type_id: fn() -> TypeId,
+    type_name: fn() -> &'static str,
+}
+
+/// Provides the [`DynAlphabet`] of each [`Alphabet`] as a constant, so that it can be
+/// referenced for `'static` without any registration step.
+struct DynAlphabetOf<const N: usize, A: Alphabet<N>>(PhantomData<A>);
+
+impl<const N: usize, A: Alphabet<N> + 'static> DynAlphabetOf<N, A> {
+    const DESCRIPTOR: &'static DynAlphabet = &DynAlphabet {
+        chars: &A::ALPHABET,
+        type_id: TypeId::of::<A>,
+        type_name: type_name::<A>,
+    };
+}
+
+impl DynAlphabet {
+    /// Returns the [`DynAlphabet`] describing the [`Alphabet`] `A`.
+    pub const fn of<const N: usize, A: Alphabet<N> + 'static>() -> &'static DynAlphabet {
+        DynAlphabetOf::<N, A>::DESCRIPTOR
+    }
+
+    /// Returns the characters of the [`Alphabet`] described by this [`DynAlphabet`], i.e. its
+    /// [`Alphabet::ALPHABET`].
+    pub const fn chars(&self) -> &'static [char] {
+        self.chars
+    }
+
+    /// Returns the (fully qualified) type name of the [`Alphabet`] described by this
+    /// [`DynAlphabet`], for diagnostic purposes.
+    pub fn name(&self) -> &'static str {
+        (self.type_name)()
+    }
+
+    /// Returns `true` if this [`DynAlphabet`] describes the [`Alphabet`] `A`.
+    pub fn is<const N: usize, A: Alphabet<N> + 'static>(&self) -> bool {
+        (self.type_id)() == TypeId::of::<A>()
+    }
+}
+
+impl PartialEq for DynAlphabet {
+    fn eq(&self, other: &Self) -> bool {
+        (self.type_id)() == (other.type_id)()
+    }
+}
+impl Eq for DynAlphabet {}
+impl Hash for DynAlphabet {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (self.type_id)().hash(state);
+    }
+}
+
+impl Debug for DynAlphabet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A [`CustomSymbol`] of any [`Alphabet`], with the [`Alphabet`] erased from its type and
+/// tracked at runtime via a [`DynAlphabet`] instead. This allows symbols from several
+/// alphabets to be stored side by side, e.g. in the same `Vec` or as keys of the same map.
+///
+/// A [`DynSymbol`] consists of the backing [`u128`] of the symbol and a reference to the
+/// [`DynAlphabet`] of its [`Alphabet`]. Two [`DynSymbol`]s are equal if they encode the same
+/// name using the same [`Alphabet`], so symbols with the same name from different alphabets
+/// are distinct. Since the encoding of a symbol doesn't depend on its [`SymbolRepr`], symbols
+/// backed by a [`u32`], [`u64`] or [`u128`] can all be converted into a [`DynSymbol`] via the
+/// corresponding [`From`] impl, and converted back using [`DynSymbol::downcast`].
+///
+/// ### Example
+#[doc = docify::embed_run!("tests/tests.rs", dyn_symbol_example)]
+#[derive(Copy, Clone)]
+pub struct DynSymbol {
+    alphabet: &'static DynAlphabet,
+    data: u128,
+}
+
+impl DynSymbol {
+    /// Returns the [`DynAlphabet`] of the [`Alphabet`] this symbol belongs to.
+    pub const fn alphabet(&self) -> &'static DynAlphabet {
+        self.alphabet
+    }
+
+    /// Returns the backing [`u128`] of this symbol, which is the same as that of the
+    /// [`CustomSymbol`] it was created from.
+    pub const fn to_raw(&self) -> u128 {
+        self.data
+    }
+
+    /// Returns `true` if this symbol belongs to the [`Alphabet`] `A`.
+    pub fn is<const N: usize, A: Alphabet<N> + 'static>(&self) -> bool {
+        self.alphabet.is::<N, A>()
+    }
+
+    /// Converts this symbol back into a [`CustomSymbol`] using the [`Alphabet`] `A` (and its
+    /// default [`SymbolRepr`]), or returns [`None`] if it belongs to a different [`Alphabet`]
+    /// or is too long for the [`Alphabet::Repr`] of `A`.
+    pub fn downcast<const N: usize, A: Alphabet<N> + 'static>(&self) -> Option<CustomSymbol<N, A>> {
+        if !self.is::<N, A>() {
+            return None;
+        }
+        let data = A::Repr::from_u128(self.data)?;
+        if !raw_is_valid::<N, A, A::Repr>(data) {
+            return None;
+        }
+        Some(CustomSymbol::from_raw_unchecked(data))
+    }
+
+    /// Returns an iterator over the [`char`]s of this symbol, decoded using the characters of
+    /// its [`DynAlphabet`].
+    pub fn chars(&self) -> impl Iterator<Item = char> {
+        let chars = self.alphabet.chars;
+        let char_size = chars.len() as u128 + 1;
+        let mut rem = self.data;
+        core::iter::from_fn(move || {
+            if rem == 0 {
+                return None;
+            }
+            let digit = rem % char_size;
+            rem /= char_size;
+            Some(chars[digit as usize - 1])
+        })
+    }
+}
+
+impl<const N: usize, A: Alphabet<N> + 'static, R: SymbolRepr + Into<u128>>
+    From<CustomSymbol<N, A, R>> for DynSymbol
+{
+    fn from(value: CustomSymbol<N, A, R>) -> Self {
+        DynSymbol {
+            alphabet: DynAlphabet::of::<N, A>(),
+            data: value.data.into(),
+        }
+    }
+}
+
+impl PartialEq for DynSymbol {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data && self.alphabet == other.alphabet
+    }
+}
+impl Eq for DynSymbol {}
+impl Hash for DynSymbol {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.alphabet.hash(state);
+        self.data.hash(state);
+    }
+}
+
+impl Debug for DynSymbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        /// Formats the name of a [`DynSymbol`] like a [`str`], without allocating.
+        struct Name<'a>(&'a DynSymbol);
+        impl Debug for Name<'_> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+                f.write_char('"')?;
+                for c in self.0.chars().flat_map(char::escape_debug) {
+                    f.write_char(c)?;
+                }
+                f.write_char('"')
+            }
+        }
+        f.debug_struct("DynSymbol")
+            .field("alphabet", &self.alphabet)
+            .field("symbol", &Name(self))
+            .finish()
+    }
+}
+
+impl Display for DynSymbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for c in self.chars() {
+            f.write_char(c)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+//! Contains [`SymbolHasher`] and [`SymbolBuildHasher`], a fast non-cryptographic hasher for
+//! collections keyed by symbols, along with the `SymbolHashMap` / `SymbolHashSet` type
+//! aliases (which require the `std` or `hashbrown` feature).
+
+use core::hash::{BuildHasher, Hasher};
+
+/// Multiplied against the folded words of each value written to a [`SymbolHasher`]. These
+/// are the first digits of pi, as also used by `foldhash`.
+const MULTIPLE: u64 = 0x243f_6a88_85a3_08d3;
+
+/// The initial state of a [`SymbolHasher`], mixed in so that writing a zero doesn't leave
+/// the state at zero.
+const SEED: u64 = 0x1319_8a2e_0370_7344;
+
+/// Multiplies two [`u64`]s into a [`u128`] and folds the two halves of the result together
+/// with XOR, which thoroughly mixes the bits of both inputs using a single multiplication.
+#[inline(always)]
+const fn folded_multiply(a: u64, b: u64) -> u64 {
+    let full = a as u128 * b as u128;
+    full as u64 ^ (full >> 64) as u64
+}
+
+/// A [`Hasher`] optimized for symbols, which are already near-unique integers and so don't
+/// need the heavy-duty (and comparatively slow) mixing performed by the default SipHash
+/// hasher used by `HashMap`.
+///
+/// Each [`u128`] written to the hasher (i.e. the backing integer of a [`Symbol`](crate::Symbol))
+/// is folded into the state using one 64-bit by 64-bit multiplication per half, which is
+/// enough to spread its bits over both the high bits (used by `hashbrown` for its control
+/// bytes) and the low bits (used to pick a bucket) of the resulting hash. Other types can still be
+/// hashed, though this is not what the hasher is tuned for.
+///
+/// Note that unlike the default hasher, [`SymbolHasher`] is not randomly seeded, so it
+/// should not be used for maps whose keys are controlled by an adversary who could
+/// deliberately cause collisions.
+#[derive(Copy, Clone, Debug)]
+pub struct SymbolHasher {
+    state: u64,
+}
+
+impl Default for SymbolHasher {
+    fn default() -> Self {
+        SymbolHasher { state: SEED }
+    }
+}
+
+impl Hasher for SymbolHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            self.write_u64(u64::from_le_bytes(word));
+        }
+        let remainder = chunks.remainder();
+        if !remainder.is_empty() {
+            let mut word = [0; 8];
+            word[..remainder.len()].copy_from_slice(remainder);
+            // the length is mixed in so that trailing zero bytes aren't ignored
+            self.write_u64(u64::from_le_bytes(word) ^ (remainder.len() as u64) << 59);
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.state = folded_multiply(self.state ^ i, MULTIPLE);
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        // each half is folded in separately, as mixing a half into the multiplier would
+        // zero it out (and so ignore the other half) whenever that half equals `MULTIPLE`
+        self.write_u64((i >> 64) as u64);
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+}
+
+/// A [`BuildHasher`] that creates [`SymbolHasher`]s, for use with `HashMap` / `HashSet` (see
+/// `SymbolHashMap` and `SymbolHashSet`) or any other collection that is generic over its
+/// [`BuildHasher`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SymbolBuildHasher;
+
+impl BuildHasher for SymbolBuildHasher {
+    type Hasher = SymbolHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> SymbolHasher {
+        SymbolHasher::default()
+    }
+}
+
+/// A `HashMap` that uses [`SymbolBuildHasher`], with keys defaulting to [`Symbol`](crate::Symbol).
+///
+/// This is `std::collections::HashMap` when the `std` feature is enabled, and
+/// `hashbrown::HashMap` when only the `hashbrown` feature is enabled.
+#[cfg(feature = "std")]
+pub type SymbolHashMap<V, K = crate::Symbol> = std::collections::HashMap<K, V, SymbolBuildHasher>;
+
+/// A `HashMap` that uses [`SymbolBuildHasher`], with keys defaulting to [`Symbol`](crate::Symbol).
+///
+/// This is `std::collections::HashMap` when the `std` feature is enabled, and
+/// `hashbrown::HashMap` when only the `hashbrown` feature is enabled.
+#[cfg(all(feature = "hashbrown", not(feature = "std")))]
+pub type SymbolHashMap<V, K = crate::Symbol> = hashbrown::HashMap<K, V, SymbolBuildHasher>;
+
+/// A `HashSet` that uses [`SymbolBuildHasher`], with values defaulting to
+/// [`Symbol`](crate::Symbol).
+///
+/// This is `std::collections::HashSet` when the `std` feature is enabled, and
+/// `hashbrown::HashSet` when only the `hashbrown` feature is enabled.
+#[cfg(feature = "std")]
+pub type SymbolHashSet<T = crate::Symbol> = std::collections::HashSet<T, SymbolBuildHasher>;
+
+/// A `HashSet` that uses [`SymbolBuildHasher`], with values defaulting to
+/// [`Symbol`](crate::Symbol).
+///
+/// This is `std::collections::HashSet` when the `std` feature is enabled, and
+/// `hashbrown::HashSet` when only the `hashbrown` feature is enabled.
+#[cfg(all(feature = "hashbrown", not(feature = "std")))]
+pub type SymbolHashSet<T = crate::Symbol> = hashbrown::HashSet<T, SymbolBuildHasher>;
@@ -0,0 +1,268 @@
+//! Contains [`CustomInternedSymbol`] / [`InternedSymbol`], which fall back to a global
+//! interner for names that can't be represented as a [`CustomSymbol`].
+
+use super::*;
+use alloc::boxed::Box;
+use std::{
+    collections::HashSet,
+    sync::{OnceLock, PoisonError, RwLock},
+};
+
+/// A [`CustomInternedSymbol`] using the [`DefaultAlphabet`], the interning counterpart of
+/// [`Symbol`].
+///
+/// ### Example
+#[doc = docify::embed_run!("tests/tests.rs", interned_symbol_example)]
+pub type InternedSymbol = CustomInternedSymbol<{ DefaultAlphabet::LEN }, DefaultAlphabet>;
+
+/// A symbol that can hold _any_ name, storing names that are valid [`CustomSymbol`]s inline
+/// exactly like a [`CustomSymbol`] does, and transparently falling back to a global,
+/// thread-safe interner for names that are too long or contain characters outside of the
+/// [`Alphabet`]. Requires the `std` feature.
+///
+/// Like [`CustomSymbol`], this is [`Copy`], and equality and hashing never look at the
+/// characters of the name: inline symbols are compared by their backing [`SymbolRepr`], and
+/// interned symbols by the address of their (unique) interned string. Since names that fit
+/// inline are never interned, each name has exactly one representation.
+///
+/// The [`Alphabet::NORMALIZATION`] rules are not applied, since that would change the name
+/// of the symbol: names are only stored inline if they consist of exactly the characters of
+/// the [`Alphabet`], so e.g. `Hello-World` is interned as-is rather than being stored as
+/// `hello_world` when using an [`Alphabet`] that folds case and replaces `-` with `_`.
+///
+/// Interned strings are leaked and live for the rest of the program, so this is intended for
+/// the (typically bounded) set of identifiers used by a program, rather than for arbitrary
+/// user input.
+#[derive(Copy, Clone)]
+pub struct CustomInternedSymbol<
+    const N: usize,
+    A: Alphabet<N>,
+    R: SymbolRepr = <A as Alphabet<N>>::Repr,
+> {
+    inner: Inner<N, A, R>,
+}
+
+#[derive(Copy, Clone)]
+enum Inner<const N: usize, A: Alphabet<N>, R: SymbolRepr> {
+    Inline(CustomSymbol<N, A, R>),
+    Interned(&'static str),
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> CustomInternedSymbol<N, A, R> {
+    /// Creates a [`CustomInternedSymbol`] from the specified name, storing it inline if it is
+    /// a valid [`CustomSymbol`] (without applying the [`Alphabet::NORMALIZATION`] rules) and
+    /// interning it otherwise.
+    pub fn new(name: &str) -> Self {
+        match Self::parse_inline(name) {
+            Some(symbol) => Self::from_symbol(symbol),
+            None => CustomInternedSymbol {
+                inner: Inner::Interned(intern(name)),
+            },
+        }
+    }
+
+    /// Parses the specified name as a [`CustomSymbol`] that can be stored inline, i.e. one
+    /// whose name is exactly the specified name, rather than its normalized form.
+    fn parse_inline(name: &str) -> Option<CustomSymbol<N, A, R>> {
+        let symbol = CustomSymbol::try_from(name).ok()?;
+        (A::NORMALIZATION.is_strict() || symbol.inline_name() == name).then_some(symbol)
+    }
+
+    /// Wraps the specified [`CustomSymbol`], which is always stored inline.
+    pub const fn from_symbol(symbol: CustomSymbol<N, A, R>) -> Self {
+        CustomInternedSymbol {
+            inner: Inner::Inline(symbol),
+        }
+    }
+
+    /// Returns the underlying [`CustomSymbol`] if this symbol is stored inline, or [`None`] if
+    /// it was interned.
+    pub const fn to_symbol(self) -> Option<CustomSymbol<N, A, R>> {
+        match self.inner {
+            Inner::Inline(symbol) => Some(symbol),
+            Inner::Interned(_) => None,
+        }
+    }
+
+    /// Returns `true` if this symbol is stored inline rather than interned.
+    pub const fn is_inline(&self) -> bool {
+        matches!(self.inner, Inner::Inline(_))
+    }
+
+    /// Returns the name of this symbol, which dereferences to a [`str`]. This never
+    /// allocates: inline symbols are decoded onto the stack (see
+    /// [`CustomSymbol::inline_name`]), while interned symbols borrow their interned string.
+    pub fn name(&self) -> InternedName<N, A, R> {
+        match self.inner {
+            Inner::Inline(symbol) => InternedName::Inline(symbol.inline_name()),
+            Inner::Interned(name) => InternedName::Interned(name),
+        }
+    }
+
+    /// Returns the interned string backing this symbol, or [`None`] if it is stored inline.
+    pub const fn as_interned_str(&self) -> Option<&'static str> {
+        match self.inner {
+            Inner::Inline(_) => None,
+            Inner::Interned(name) => Some(name),
+        }
+    }
+}
+
+/// Returns the unique `'static` copy of the specified name from the global interner, leaking
+/// the name if it hasn't been interned yet.
+fn intern<S: AsRef<str> + Into<Box<str>>>(name: S) -> &'static str {
+    static INTERNER: OnceLock<RwLock<HashSet<&'static str>>> = OnceLock::new();
+    let interner = INTERNER.get_or_init(Default::default);
+    // the set is never left in an inconsistent state, so poisoning can be ignored
+    if let Some(interned) = interner
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(name.as_ref())
+    {
+        return interned;
+    }
+    let mut interner = interner.write().unwrap_or_else(PoisonError::into_inner);
+    // another thread may have interned the same name while we were waiting for the lock
+    if let Some(interned) = interner.get(name.as_ref()) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(name.into());
+    interner.insert(interned);
+    interned
+}
+
+/// The name of a [`CustomInternedSymbol`], as returned by [`CustomInternedSymbol::name`].
+/// Dereferences to [`str`].
+#[derive(Copy, Clone)]
+pub enum InternedName<const N: usize, A: Alphabet<N>, R: SymbolRepr = <A as Alphabet<N>>::Repr> {
+    /// The decoded name of an inline symbol.
+    Inline(SymbolName<N, A, R>),
+    /// The interned name of an interned symbol.
+    Interned(&'static str),
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> InternedName<N, A, R> {
+    /// Returns the contents of this [`InternedName`] as a [`str`].
+    pub fn as_str(&self) -> &str {
+        match self {
+            InternedName::Inline(name) => name.as_str(),
+            InternedName::Interned(name) => name,
+        }
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Deref for InternedName<N, A, R> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> AsRef<str> for InternedName<N, A, R> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> PartialEq for InternedName<N, A, R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Eq for InternedName<N, A, R> {}
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> PartialEq<str> for InternedName<N, A, R> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> PartialEq<&str> for InternedName<N, A, R> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Debug for InternedName<N, A, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Display for InternedName<N, A, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> From<CustomSymbol<N, A, R>>
+    for CustomInternedSymbol<N, A, R>
+{
+    fn from(value: CustomSymbol<N, A, R>) -> Self {
+        Self::from_symbol(value)
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> From<&str> for CustomInternedSymbol<N, A, R> {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> From<String> for CustomInternedSymbol<N, A, R> {
+    /// Equivalent to [`CustomInternedSymbol::new`], but reuses the allocation of the
+    /// [`String`] if the name needs to be interned for the first time.
+    fn from(value: String) -> Self {
+        match Self::parse_inline(&value) {
+            Some(symbol) => Self::from_symbol(symbol),
+            None => CustomInternedSymbol {
+                inner: Inner::Interned(intern(value)),
+            },
+        }
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> FromStr for CustomInternedSymbol<N, A, R> {
+    type Err = core::convert::Infallible;
+
+    /// Equivalent to [`CustomInternedSymbol::new`], which never fails.
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        Ok(Self::new(s))
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> PartialEq for CustomInternedSymbol<N, A, R> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.inner, other.inner) {
+            (Inner::Inline(a), Inner::Inline(b)) => a == b,
+            (Inner::Interned(a), Inner::Interned(b)) => core::ptr::eq(a, b),
+            _ => false,
+        }
+    }
+}
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Eq for CustomInternedSymbol<N, A, R> {}
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Hash for CustomInternedSymbol<N, A, R> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        match self.inner {
+            Inner::Inline(symbol) => symbol.hash(state),
+            Inner::Interned(name) => state.write_usize(name.as_ptr() as usize),
+        }
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Debug for CustomInternedSymbol<N, A, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("InternedSymbol")
+            .field("inline", &self.is_inline())
+            .field("symbol", &self.name())
+            .finish()
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Display for CustomInternedSymbol<N, A, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.inner {
+            Inner::Inline(symbol) => Display::fmt(&symbol, f),
+            Inner::Interned(name) => f.write_str(name),
+        }
+    }
+}
@@ -0,0 +1,332 @@
+//! Contains [`CustomLexSymbol`] / [`LexSymbol`], an alternative encoding of [`CustomSymbol`]
+//! whose backing [`u128`] sorts in the same order as the symbol's name.
+
+use super::*;
+use core::ops::RangeInclusive;
+
+#[cfg(feature = "alloc")]
+use alloc::collections::{btree_map, btree_set, BTreeMap, BTreeSet};
+
+/// A [`CustomLexSymbol`] using the [`DefaultAlphabet`], the lexically-ordered counterpart of
+/// [`Symbol`].
+///
+/// ### Example
+#[doc = docify::embed_run!("tests/tests.rs", lex_symbol_example)]
+pub type LexSymbol = CustomLexSymbol<{ DefaultAlphabet::LEN }, DefaultAlphabet>;
+
+/// An alternative encoding of [`CustomSymbol`] in which the numeric order of the backing
+/// [`u128`] matches the lexicographic order of the symbol's name, i.e. the same order you
+/// would get by sorting the results of [`CustomLexSymbol::name`] as [`String`]s.
+///
+/// [`CustomSymbol`] encodes the first character of a symbol in the _least_ significant
+/// base-`(N + 1)` digit of its [`u128`], which makes encoding and decoding cheap but means
+/// that (for example) `s!(b) > s!(ab)`. [`CustomLexSymbol`] instead encodes the first
+/// character in the _most_ significant digit, left-aligning the symbol so that shorter
+/// symbols sort before any longer symbols they are a prefix of, and numbers characters
+/// according to their [`char`] order rather than their order in the [`Alphabet`].
+///
+/// [`CustomLexSymbol`] is always backed by a [`u128`], so it can only be converted to and from
+/// [`CustomSymbol`]s that use a [`u128`] as their [`SymbolRepr`].
+///
+/// Converting between the two encodings is cheap and infallible, and can be done via
+/// [`CustomLexSymbol::from_symbol`] / [`CustomLexSymbol::to_symbol`] (both `const`) or the
+/// corresponding [`From`] impls. Together with [`CustomSymbol::try_from_raw`] and
+/// [`CustomLexSymbol::try_from_raw`], which validate raw [`u128`]s in either encoding, this
+/// also serves as a migration path for raw values that were persisted using one of the two.
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct CustomLexSymbol<const N: usize, A: Alphabet<N>> {
+    _alphabet: PhantomData<A>,
+    data: u128,
+}
+
+impl<const N: usize, A: Alphabet<N>> CustomLexSymbol<N, A> {
+    /// For each (0-based) index in the [`Alphabet`], the 1-based rank of that character when
+    /// all characters in the [`Alphabet`] are sorted by [`char`] order.
+    const RANKS: [u128; N] = {
+        let mut ranks = [0; N];
+        let mut i = 0;
+        while i < N {
+            let mut rank = 1;
+            let mut j = 0;
+            while j < N {
+                if (A::ALPHABET[j] as u32) < (A::ALPHABET[i] as u32) {
+                    rank += 1;
+                }
+                j += 1;
+            }
+            ranks[i] = rank;
+            i += 1;
+        }
+        ranks
+    };
+
+    /// The inverse of [`Self::RANKS`], mapping each (0-based) rank to the 1-based index of the
+    /// corresponding character in the [`Alphabet`].
+    const INDICES: [u128; N] = {
+        let mut indices = [0; N];
+        let mut i = 0;
+        while i < N {
+            indices[Self::RANKS[i] as usize - 1] = i as u128 + 1;
+            i += 1;
+        }
+        indices
+    };
+
+    /// Creates a [`CustomLexSymbol`] directly from a raw [`u128`] that is assumed to already
+    /// use the lexical encoding, _without_ checking that it decodes to a valid symbol.
+    ///
+    /// Decoding an invalid value (e.g. via [`CustomLexSymbol::to_symbol`] or the [`Display`]
+    /// impl) panics or produces a meaningless name, so untrusted values (such as those read
+    /// from the network or from disk) should always go through
+    /// [`CustomLexSymbol::try_from_raw`] or the corresponding [`TryFrom`] impl instead.
+    pub const fn from_raw_unchecked(data: u128) -> Self {
+        CustomLexSymbol {
+            _alphabet: PhantomData,
+            data,
+        }
+    }
+
+    /// Creates a [`CustomLexSymbol`] from a raw [`u128`] using the lexical encoding, such as
+    /// one previously obtained via the corresponding [`From`] impl, in a `const` context. This
+    /// is the validating counterpart of [`CustomLexSymbol::from_raw_unchecked`], and is also
+    /// available via the corresponding [`TryFrom`] impl.
+    ///
+    /// Symbols are left-aligned to exactly [`CustomSymbol::MAX_SYMBOL_LEN`] base-`(N + 1)`
+    /// digits, so `data` must consist of at most that many non-zero digits followed by zero
+    /// digits as padding, with nothing in between, so that any symbol returned by this
+    /// function can be decoded without panicking.
+    pub const fn try_from_raw(data: u128) -> core::result::Result<Self, SymbolParsingError> {
+        let char_size = N as u128 + 1;
+        let max = CustomSymbol::<N, A, u128>::MAX_SYMBOL_LEN;
+        if data == 0 {
+            return Err(SymbolParsingError::Empty);
+        }
+        let mut rem = data;
+        let mut padding = 0;
+        while rem.is_multiple_of(char_size) {
+            rem /= char_size;
+            padding += 1;
+        }
+        let mut len = 0;
+        while rem != 0 {
+            if rem.is_multiple_of(char_size) {
+                return Err(SymbolParsingError::InvalidRaw);
+            }
+            rem /= char_size;
+            len += 1;
+        }
+        if len > max {
+            return Err(SymbolParsingError::TooLong { len, max });
+        }
+        if len + padding != max {
+            return Err(SymbolParsingError::InvalidRaw);
+        }
+        Ok(CustomLexSymbol::from_raw_unchecked(data))
+    }
+
+    /// Re-encodes the specified [`CustomSymbol`] as a [`CustomLexSymbol`].
+    pub const fn from_symbol(symbol: CustomSymbol<N, A, u128>) -> Self {
+        let char_size = N as u128 + 1;
+        let mut rem = symbol.data;
+        let mut data: u128 = 0;
+        let mut len = 0;
+        while rem != 0 {
+            let digit = rem % char_size;
+            rem /= char_size;
+            data = data * char_size + Self::RANKS[digit as usize - 1];
+            len += 1;
+        }
+        while len < CustomSymbol::<N, A, u128>::MAX_SYMBOL_LEN {
+            data *= char_size;
+            len += 1;
+        }
+        CustomLexSymbol::from_raw_unchecked(data)
+    }
+
+    /// Re-encodes this [`CustomLexSymbol`] as a regular [`CustomSymbol`].
+    pub const fn to_symbol(self) -> CustomSymbol<N, A, u128> {
+        let char_size = N as u128 + 1;
+        let mut rem = self.data;
+        while rem != 0 && rem.is_multiple_of(char_size) {
+            rem /= char_size;
+        }
+        let mut data: u128 = 0;
+        while rem != 0 {
+            let rank = rem % char_size;
+            rem /= char_size;
+            data = data * char_size + Self::INDICES[rank as usize - 1];
+        }
+        CustomSymbol::from_raw_unchecked(data)
+    }
+
+    /// Returns the range of all [`CustomLexSymbol`]s that start with this symbol (including
+    /// this symbol itself). Since the first character of a [`CustomLexSymbol`] is stored in
+    /// the most significant digit, these always form a contiguous range, so this can be passed
+    /// to e.g. `BTreeMap::range` to find all keys with a given prefix without decoding any of
+    /// them. See [`SymbolPrefixExt`] for a more convenient way to do this.
+    pub const fn prefix_range(&self) -> RangeInclusive<Self> {
+        let char_size = N as u128 + 1;
+        let mut rem = self.data;
+        // `fill` is the largest value of the digits following the prefix, i.e. every one of
+        // them set to the highest ranked character in the alphabet
+        let mut fill: u128 = 0;
+        let mut len = 0;
+        while len < CustomSymbol::<N, A, u128>::MAX_SYMBOL_LEN && rem.is_multiple_of(char_size) {
+            rem /= char_size;
+            fill = fill * char_size + N as u128;
+            len += 1;
+        }
+        RangeInclusive::new(*self, CustomLexSymbol::from_raw_unchecked(self.data + fill))
+    }
+
+    /// Converts this [`CustomLexSymbol`] into a human-readable [`String`] representation.
+    #[cfg(feature = "alloc")]
+    pub fn name(&self) -> String {
+        self.to_symbol().name()
+    }
+
+    /// Converts this [`CustomLexSymbol`] into a human-readable [`SymbolName`], without
+    /// allocating.
+    pub fn inline_name(&self) -> SymbolName<N, A, u128> {
+        self.to_symbol().inline_name()
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>> CustomSymbol<N, A, u128> {
+    /// Compares two [`CustomSymbol`]s lexicographically by name (as opposed to the [`Ord`] impl
+    /// on [`CustomSymbol`], which compares the backing [`u128`]s), without allocating.
+    ///
+    /// If you need to do this frequently (for example to keep symbols sorted in a `BTreeMap`),
+    /// consider using [`CustomLexSymbol`] instead.
+    pub fn lex_cmp(&self, other: &Self) -> core::cmp::Ordering {
+        CustomLexSymbol::from_symbol(*self).cmp(&CustomLexSymbol::from_symbol(*other))
+    }
+}
+
+/// Extends ordered collections keyed by [`CustomLexSymbol`]s (i.e. `BTreeMap` and `BTreeSet`)
+/// with prefix queries, which are answered using [`CustomLexSymbol::prefix_range`] rather than
+/// by scanning and decoding every key. Requires the `alloc` feature.
+///
+/// ### Example
+#[doc = docify::embed_run!("tests/tests.rs", symbol_prefix_example)]
+#[cfg(feature = "alloc")]
+pub trait SymbolPrefixExt<const N: usize, A: Alphabet<N>> {
+    /// The iterator returned by [`SymbolPrefixExt::starting_with`].
+    type Iter<'a>: Iterator
+    where
+        Self: 'a;
+
+    /// Returns an iterator over the entries of this collection whose keys start with the
+    /// specified prefix (which can be a [`CustomSymbol`] or a [`CustomLexSymbol`]), in order.
+    fn starting_with(&self, prefix: impl Into<CustomLexSymbol<N, A>>) -> Self::Iter<'_>;
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize, A: Alphabet<N>, V> SymbolPrefixExt<N, A>
+    for BTreeMap<CustomLexSymbol<N, A>, V>
+{
+    type Iter<'a>
+        = btree_map::Range<'a, CustomLexSymbol<N, A>, V>
+    where
+        Self: 'a;
+
+    fn starting_with(&self, prefix: impl Into<CustomLexSymbol<N, A>>) -> Self::Iter<'_> {
+        self.range(prefix.into().prefix_range())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize, A: Alphabet<N>> SymbolPrefixExt<N, A> for BTreeSet<CustomLexSymbol<N, A>> {
+    type Iter<'a>
+        = btree_set::Range<'a, CustomLexSymbol<N, A>>
+    where
+        Self: 'a;
+
+    fn starting_with(&self, prefix: impl Into<CustomLexSymbol<N, A>>) -> Self::Iter<'_> {
+        self.range(prefix.into().prefix_range())
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>> From<CustomSymbol<N, A, u128>> for CustomLexSymbol<N, A> {
+    fn from(value: CustomSymbol<N, A, u128>) -> Self {
+        CustomLexSymbol::from_symbol(value)
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>> From<CustomLexSymbol<N, A>> for CustomSymbol<N, A, u128> {
+    fn from(value: CustomLexSymbol<N, A>) -> Self {
+        value.to_symbol()
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>> From<CustomLexSymbol<N, A>> for u128 {
+    fn from(value: CustomLexSymbol<N, A>) -> Self {
+        value.data
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>> TryFrom<u128> for CustomLexSymbol<N, A> {
+    type Error = SymbolParsingError;
+
+    /// Equivalent to [`CustomLexSymbol::try_from_raw`].
+    fn try_from(value: u128) -> core::result::Result<Self, Self::Error> {
+        Self::try_from_raw(value)
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>> TryFrom<&str> for CustomLexSymbol<N, A> {
+    type Error = SymbolParsingError;
+
+    /// Attempts to interpret the provided string as a valid [`CustomLexSymbol`], following
+    /// the same rules as [`CustomSymbol`].
+    fn try_from(value: &str) -> core::result::Result<Self, Self::Error> {
+        CustomSymbol::try_from(value).map(CustomLexSymbol::from_symbol)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize, A: Alphabet<N>> TryFrom<String> for CustomLexSymbol<N, A> {
+    type Error = SymbolParsingError;
+
+    fn try_from(value: String) -> core::result::Result<Self, Self::Error> {
+        CustomLexSymbol::try_from(value.as_str())
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>> PartialEq for CustomLexSymbol<N, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+impl<const N: usize, A: Alphabet<N>> Eq for CustomLexSymbol<N, A> {}
+impl<const N: usize, A: Alphabet<N>> Hash for CustomLexSymbol<N, A> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+    }
+}
+impl<const N: usize, A: Alphabet<N>> PartialOrd for CustomLexSymbol<N, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<const N: usize, A: Alphabet<N>> Ord for CustomLexSymbol<N, A> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.data.cmp(&other.data)
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>> Debug for CustomLexSymbol<N, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("LexSymbol")
+            .field("data", &self.data)
+            .field("symbol", &self.inline_name())
+            .finish()
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>> Display for CustomLexSymbol<N, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(&self.to_symbol(), f)
+    }
+}
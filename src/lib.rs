@@ -11,21 +11,69 @@
 //!
 //! Where this crate differs is the alphabet and length of our [`Symbol`] is a bit more
 //! restrictive, allowing us to encode the entire text of each [`Symbol`] as a [`u128`]
-//! internally. The only caveat is we are limited to 25 characters of length and an alphabet
+//! internally. The only caveat is we are limited to 26 characters of length and an alphabet
 //! consisting of lowercase a-z as well as `_`.
 //!
 //! The [`Symbol`] type can be created at compile-time using the convenient [`s!`] macro, and
 //! can also be created using the [`From<Into<String>>`] impl at runtime, though this is not as
-//! efficient as using the [`s!`] macro.
+//! efficient as using the [`s!`] macro. Symbols can also be parsed from arbitrary string
+//! expressions (such as the output of [`concat!`]) in `const` contexts using
+//! [`CustomSymbol::try_from_str`].
 //!
-//! The [`Symbol`] type can also be turned into a [`String`] via a convenient [`Into<String>`].
+//! The [`Symbol`] type can also be turned into a `String` via a convenient `Into<String>`, or
+//! into a stack-allocated [`SymbolName`] via [`CustomSymbol::inline_name`]. The `String`
+//! conversions require the `alloc` feature (enabled by default), while everything else
+//! (including the [`Display`] impl, which never allocates) works without a heap. Individual
+//! characters can be inspected without decoding the whole name via
+//! [`CustomSymbol::chars`], [`CustomSymbol::char_at`], [`CustomSymbol::len`] and friends,
+//! and symbols can be combined and taken apart using `const` arithmetic on their backing
+//! integers via [`CustomSymbol::concat`], [`CustomSymbol::strip_prefix`],
+//! [`CustomSymbol::slice`] and friends (or at compile time via `s!(user + "_" + created)`).
 //!
 //! We also provide the ability to define custom alphabets that use the more general
 //! [`CustomSymbol`] type via a handy [`custom_alphabet!`] macro, allowing you to alter these
 //! restrictions directly (smaller alphabet = larger max length for a symbol) and add support
-//! for other languages or less restrictive character sets. The only invariant that can't be
-//! customized at the moment is [`CustomSymbol`] will always use a [`u128`] as its backing data
-//! store.
+//! for other languages or less restrictive character sets. A number of ready-made alphabets
+//! are provided as well: [`AlphaNumeric`], [`Base32Crockford`], [`Hex`], [`UpperSnake`],
+//! [`Kebab`] and [`DottedPath`]. Alphabets can also normalize their
+//! input (e.g. folding case or accepting `-` in place of `_`), see [`Normalization`], and
+//! symbols can be re-encoded from one alphabet to another via [`CustomSymbol::convert`].
+//!
+//! The backing integer can be customized as well: [`CustomSymbol`] takes an optional third
+//! type parameter implementing [`SymbolRepr`] ([`u32`], [`u64`], [`u128`] or [`U256`]),
+//! which defaults to the [`Alphabet::Repr`] chosen when defining the alphabet (a [`u128`]
+//! unless specified otherwise). Narrower integers take up less space, while wider ones allow
+//! for longer symbols.
+//!
+//! Symbols can also be used as patterns in `match`-like expressions via the [`sym_match!`]
+//! macro, which matches directly on the backing integer, and unit-only enums can be mapped
+//! to and from symbols using [`#[derive(Symbolic)]`](derive@Symbolic). Static lookup tables
+//! keyed by symbols can be built at compile time using the [`symbol_map!`] and
+//! [`symbol_set!`] macros, which produce a [`SymbolMap`] / [`SymbolSet`] backed by a perfect
+//! hash function.
+//!
+//! [`Symbol`]s are ordered by their backing [`u128`], which does not match the lexicographic
+//! order of their names. If you need symbols that sort by name (for example as keys in a
+//! `BTreeMap`), the [`LexSymbol`] / [`CustomLexSymbol`] types provide an alternative
+//! encoding with this property, and can be cheaply converted to and from [`Symbol`]. Since
+//! all [`LexSymbol`]s sharing a prefix form a contiguous range (see
+//! [`CustomLexSymbol::prefix_range`]), prefix queries on ordered collections are cheap too.
+//!
+//! Symbols from different alphabets can be stored side by side (e.g. in the same `Vec` or
+//! as keys of the same map) by converting them into [`DynSymbol`]s, which track their
+//! [`Alphabet`] at runtime and can be downcast back into [`CustomSymbol`]s.
+//!
+//! Names that don't fit in a [`Symbol`] (because they are too long or use characters outside
+//! of the alphabet) can be handled using `InternedSymbol`, which stores valid symbols inline
+//! and falls back to a global interner for everything else (requires the `std` feature).
+//!
+//! Symbols already hash to near-unique integers, so rather than the default SipHash hasher,
+//! `HashMap`s keyed by symbols can use the much cheaper [`SymbolHasher`] via
+//! [`SymbolBuildHasher`], or the `SymbolHashMap` / `SymbolHashSet` aliases provided by the
+//! optional `std` and `hashbrown` features.
+//!
+//! Enabling the optional `serde` feature provides `Serialize` / `Deserialize` impls for
+//! [`CustomSymbol`], see the `serialization` module for details.
 //!
 //! ### Example
 #![doc = docify::embed_run!("tests/tests.rs", symbol_type_example)]
@@ -37,18 +85,49 @@
 #[cfg(all(doc, feature = "generate-readme"))]
 docify::compile_markdown!("README.docify.md", "README.md");
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
 use alloc::string::String;
 use core::{
-    fmt::{Debug, Display, Formatter, Result},
+    fmt::{Debug, Display, Formatter, Result, Write},
     hash::Hash,
     marker::PhantomData,
+    ops::Deref,
+    str::FromStr,
 };
 
 pub use smol_symbol_macros::*;
 
-/// A compact representation for a (maximum of) 25-character identifier consisting of only
+mod dynamic;
+pub use dynamic::*;
+
+mod hash;
+pub use hash::*;
+
+#[cfg(feature = "std")]
+mod interned;
+#[cfg(feature = "std")]
+pub use interned::*;
+
+mod lex;
+pub use lex::*;
+
+mod map;
+pub use map::*;
+
+mod repr;
+use repr::ReprOps;
+pub use repr::*;
+
+#[cfg(feature = "serde")]
+pub mod serialization;
+
+/// A compact representation for a (maximum of) 26-character identifier consisting of only
 /// lowercase a-z as well as `_`. Internally this data is converted to a [`u128`], allowing for
 /// trivial comparison operations between symbols.
 ///
@@ -82,120 +161,1051 @@ pub trait Alphabet<const N: usize>: Copy + Clone + PartialEq + Eq {
     /// [`Alphabet`] as a [`u128`], for performance reasons.
     const LEN_U218: u128 = Self::LEN as u128;
 
+    /// The [`SymbolRepr`] used by default by [`CustomSymbol`]s using this [`Alphabet`]. This
+    /// is a [`u128`] unless a different one is passed to the [`custom_alphabet!`] macro.
+    type Repr: SymbolRepr;
+
     /// Auto-generated constant that determines the maximum length a [`CustomSymbol`] using
-    /// this [`Alphabet`] could be, based on the number of bits used per symbol character.
-    const MAX_SYMBOL_LEN: usize = 128 / ceil_log2(Self::LEN + 1);
+    /// this [`Alphabet`] and its default [`Alphabet::Repr`] could be, i.e. the largest `k`
+    /// such that `(N + 1)^k <= 2^BITS`, since each character is stored as a base-`(N + 1)`
+    /// digit of the backing integer. See `CustomSymbol::MAX_SYMBOL_LEN` for other
+    /// [`SymbolRepr`]s.
+    const MAX_SYMBOL_LEN: usize =
+        max_symbol_len(Self::LEN_U218 + 1, <Self::Repr as SymbolRepr>::BITS);
+
+    /// Fixed-size byte arrays large enough to hold the UTF-8 encoding of the longest possible
+    /// [`CustomSymbol`] using this [`Alphabet`] and a [`u32`], [`u64`], [`u128`] or [`U256`]
+    /// backing respectively, used as the backing for [`SymbolName`]. Automatically sized by
+    /// the [`custom_alphabet!`] macro.
+    #[doc(hidden)]
+    type NameBuffer32: NameBuffer;
+    #[doc(hidden)]
+    type NameBuffer64: NameBuffer;
+    #[doc(hidden)]
+    type NameBuffer128: NameBuffer;
+    #[doc(hidden)]
+    type NameBuffer256: NameBuffer;
+
+    /// The [`Normalization`] rules applied to input before it is parsed as a [`CustomSymbol`]
+    /// using this [`Alphabet`]. Defaults to [`Normalization::STRICT`], and can be customized
+    /// via the `normalize(...)` option of the [`custom_alphabet!`] macro.
+    const NORMALIZATION: Normalization = Normalization::STRICT;
 
-    /// Returns the 1-based (0 is reserved) index of this [`char`] in this [`Alphabet`]. An
+    /// Returns the 1-based (0 is reserved) index of this [`char`] in this [`Alphabet`], or a
+    /// [`SymbolParsingError::InvalidChar`] if the [`char`] is not in this [`Alphabet`]. An
     /// automatic implementation of this is provided by the [`custom_alphabet!`] macro.
+    ///
+    /// Characters listed in the [`Normalization::aliases`] of this [`Alphabet`] resolve to
+    /// the index of the character they are an alias for.
     fn invert_char(c: char) -> core::result::Result<u128, SymbolParsingError>;
 }
 
-custom_alphabet!(DefaultAlphabet, abcdefghijklmnopqrstuvwxyz_);
+/// Describes how input is normalized before being parsed as a [`CustomSymbol`] using a
+/// particular [`Alphabet`] (see [`Alphabet::NORMALIZATION`]). These rules are applied
+/// consistently by the [`TryFrom<&str>`] / [`FromStr`] impls, [`CustomSymbol::try_from_str`],
+/// [`CustomSymbol::try_from_chars`] and thus the [`s!`] macro, so that (for example)
+/// `Hello-World` and `hello_world` can be made to parse to the same symbol.
+///
+/// Normalization only affects parsing: symbols always decode to the characters of their
+/// [`Alphabet`], so a normalized symbol's name is its canonical form. The
+/// [`CustomSymbol::try_from_str_strict`] function ignores these rules entirely.
+///
+/// Usually created by the [`custom_alphabet!`] macro, e.g.:
+///
+/// ```ignore
+/// custom_alphabet!(
+///     CliAlphabet,
+///     abcdefghijklmnopqrstuvwxyz_,
+///     normalize(fold_case, '-' | ' ' => '_', trim)
+/// );
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Normalization {
+    /// Whether leading and trailing ASCII whitespace is ignored.
+    pub trim: bool,
+    /// Characters that are accepted in place of characters of the [`Alphabet`], as
+    /// `(alias, char)` pairs. Case folding is implemented by aliasing the other case of
+    /// each character in the [`Alphabet`].
+    pub aliases: &'static [(char, char)],
+}
+
+impl Normalization {
+    /// No normalization at all, i.e. only the exact characters of the [`Alphabet`] are
+    /// accepted. This is the default.
+    pub const STRICT: Normalization = Normalization {
+        trim: false,
+        aliases: &[],
+    };
+
+    /// Returns `true` if these are the [`Normalization::STRICT`] rules.
+    pub const fn is_strict(&self) -> bool {
+        !self.trim && self.aliases.is_empty()
+    }
+}
+
+/// Used internally by the macros in this crate to refer to the length and [`Alphabet::Repr`]
+/// of an [`Alphabet`] without knowing its length ahead of time. Implemented automatically by
+/// the [`custom_alphabet!`] macro.
+#[doc(hidden)]
+pub trait __AlphabetInfo {
+    const __LEN: usize;
+    type __Repr: SymbolRepr;
+}
+
+/// Implemented by the [`custom_alphabet!`] macro to record that every [`CustomSymbol`] using
+/// the [`Alphabet`] `B` (backed by its default [`SymbolRepr`]) is also a valid
+/// [`CustomSymbol`] using `Self`, i.e. that `B` is a subset of `Self` and that its symbols
+/// are never longer than `Self` allows. This enables the corresponding [`From`] impls.
+#[doc(hidden)]
+pub trait __Contains<const M: usize, B: Alphabet<M>> {}
+
+/// Implements [`From`] conversions to and from the specified [`Alphabet`]s, for each
+/// [`Alphabet`] that [`__Contains`] (or is contained in) them. These have to be blanket impls
+/// provided by this crate, since a [`From`] impl between two [`CustomSymbol`]s generated by
+/// [`custom_alphabet!`] in another crate would violate the orphan rules.
+macro_rules! impl_alphabet_conversions {
+    ($($alphabet:ident),*) => {
+        $(
+            impl<const M: usize, B: Alphabet<M> + __Contains<{ $alphabet::LEN }, $alphabet>>
+                From<CustomSymbol<{ $alphabet::LEN }, $alphabet>> for CustomSymbol<M, B>
+            {
+                fn from(value: CustomSymbol<{ $alphabet::LEN }, $alphabet>) -> Self {
+                    match value.convert() {
+                        Ok(symbol) => symbol,
+                        Err(err) => unreachable!("{err}"),
+                    }
+                }
+            }
+
+            impl<const N: usize, A: Alphabet<N>> From<CustomSymbol<N, A>>
+                for CustomSymbol<{ $alphabet::LEN }, $alphabet>
+            where
+                $alphabet: __Contains<N, A>,
+            {
+                fn from(value: CustomSymbol<N, A>) -> Self {
+                    match value.convert() {
+                        Ok(symbol) => symbol,
+                        Err(err) => unreachable!("{err}"),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+custom_alphabet!(
+    /// The [`Alphabet`] used by [`Symbol`], consisting of lowercase a-z and `_`.
+    DefaultAlphabet,
+    abcdefghijklmnopqrstuvwxyz_
+);
+
+custom_alphabet!(
+    /// An [`Alphabet`] consisting of the digits 0-9, uppercase A-Z and lowercase a-z, allowing
+    /// for symbols of up to 21 characters.
+    AlphaNumeric,
+    ['0'..='9', 'A'..='Z', 'a'..='z']
+);
+
+custom_alphabet!(
+    /// An [`Alphabet`] consisting of the 32 characters of
+    /// [Crockford's Base32](https://www.crockford.com/base32.html), i.e. the digits 0-9 and
+    /// the uppercase letters A-Z except for I, L, O and U, allowing for symbols of up to 25
+    /// characters.
+    ///
+    /// As per the specification, lowercase letters are accepted as well, as are `I` / `L`
+    /// (read as `1`) and `O` (read as `0`), though symbols always decode to the canonical
+    /// characters.
+    Base32Crockford,
+    ['0'..='9', 'A'..='H', 'J', 'K', 'M', 'N', 'P'..='T', 'V'..='Z'],
+    normalize(fold_case, 'O' | 'o' => '0', 'I' | 'i' | 'L' | 'l' => '1')
+);
+
+custom_alphabet!(
+    /// An [`Alphabet`] consisting of the lowercase hexadecimal digits 0-9 and a-f, allowing
+    /// for symbols of up to 31 characters.
+    Hex,
+    ['0'..='9', 'a'..='f']
+);
+
+custom_alphabet!(
+    /// An [`Alphabet`] for `UPPER_SNAKE_CASE` identifiers, consisting of uppercase A-Z, the
+    /// digits 0-9 and `_`, allowing for symbols of up to 24 characters.
+    UpperSnake,
+    ['A'..='Z', '0'..='9', '_']
+);
+
+custom_alphabet!(
+    /// An [`Alphabet`] for `kebab-case` identifiers, consisting of lowercase a-z, the digits
+    /// 0-9 and `-`, allowing for symbols of up to 24 characters.
+    Kebab,
+    ['a'..='z', '0'..='9', '-']
+);
+
+custom_alphabet!(
+    /// An [`Alphabet`] for dotted paths such as `config.server.port_2`, consisting of
+    /// lowercase a-z, the digits 0-9, `_` and `.`, allowing for symbols of up to 24
+    /// characters.
+    DottedPath,
+    ['a'..='z', '0'..='9', '_', '.']
+);
+
+impl_alphabet_conversions!(
+    DefaultAlphabet,
+    AlphaNumeric,
+    Base32Crockford,
+    Hex,
+    UpperSnake,
+    Kebab,
+    DottedPath
+);
+
+__check_known_alphabets!();
 
 /// The base type used for [`Symbol`] and any custom [`Alphabet`]'s that have been created
 /// using [`custom_alphabet!`].
 ///
+/// The optional `R` parameter determines the [`SymbolRepr`] (i.e. integer type) used to store
+/// the symbol, and defaults to the [`Alphabet::Repr`] of the alphabet.
+///
 /// Typically to create a [`Symbol`] or [`CustomSymbol`], you will want to use the [`s!`] macro.
 #[derive(Copy, Clone)]
 #[repr(transparent)]
-pub struct CustomSymbol<const N: usize, A: Alphabet<N>> {
+pub struct CustomSymbol<const N: usize, A: Alphabet<N>, R: SymbolRepr = <A as Alphabet<N>>::Repr> {
     _alphabet: PhantomData<A>,
-    data: u128,
+    data: R,
 }
 
-impl<const N: usize, A: Alphabet<N>> CustomSymbol<N, A> {
-    /// Used internally by the [`s!`] macro to create a [`Symbol`] or [`CustomSymbol`] from a
-    /// raw [`u128`] generated by the macro's interaction with some const fns.
-    pub const fn from_raw(data: u128) -> Self {
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> CustomSymbol<N, A, R> {
+    /// The maximum length of a [`CustomSymbol`] using this [`Alphabet`] and [`SymbolRepr`],
+    /// i.e. the largest `k` such that `(N + 1)^k <= 2^R::BITS`.
+    pub const MAX_SYMBOL_LEN: usize = max_symbol_len(N as u128 + 1, R::BITS);
+
+    /// Creates a [`Symbol`] or [`CustomSymbol`] directly from a raw [`SymbolRepr`] _without_
+    /// checking that it decodes to a valid symbol in this [`Alphabet`].
+    ///
+    /// This is only intended for raw values that are known to be valid, such as those
+    /// previously returned by [`CustomSymbol::to_raw`] in the same program. Decoding an
+    /// invalid value (e.g. via [`CustomSymbol::name`] or the [`Display`] impl) panics or
+    /// produces a meaningless name, so untrusted values (such as those read from the network
+    /// or from disk) should always go through [`CustomSymbol::try_from_raw`] or the
+    /// corresponding [`TryFrom`] impl instead.
+    pub const fn from_raw_unchecked(data: R) -> Self {
         CustomSymbol {
             _alphabet: PhantomData,
             data,
         }
     }
 
+    /// Creates a [`Symbol`] or [`CustomSymbol`] directly from a raw [`SymbolRepr`], without
+    /// checking that it is valid. Equivalent to [`CustomSymbol::from_raw_unchecked`].
+    #[deprecated(note = "use `from_raw_unchecked`, or `try_from_raw` for untrusted values")]
+    pub const fn from_raw(data: R) -> Self {
+        Self::from_raw_unchecked(data)
+    }
+
+    /// Returns the raw [`SymbolRepr`] backing this [`Symbol`] / [`CustomSymbol`]. This is the
+    /// same as the [`From<CustomSymbol>`] impl on the [`SymbolRepr`], but can be used in
+    /// `const` contexts.
+    pub const fn to_raw(self) -> R {
+        self.data
+    }
+
+    /// Used internally by the [`sym_match!`] macro to obtain the raw [`SymbolRepr`] of the
+    /// symbol being matched, while ensuring it has the same type as the symbols in the match
+    /// arms.
+    #[doc(hidden)]
+    pub const fn __match_raw(self, _arm: Self) -> R {
+        self.data
+    }
+
     /// Converts this [`Symbol`] or [`CustomSymbol`] into a human-readable [`String`]
-    /// representation. This is only possible because the [`u128`] used as the backing for
+    /// representation. This is only possible because the integer used as the backing for
     /// [`CustomSymbol`] encodes all bits of information for each character in the
     /// [`CustomSymbol`].
+    ///
+    /// See [`CustomSymbol::inline_name`] for a version of this that does not allocate.
+    #[cfg(feature = "alloc")]
     pub fn name(&self) -> String {
+        let mut result = String::with_capacity(Self::MAX_SYMBOL_LEN);
+        result.extend(self.chars());
+        result
+    }
+
+    /// Converts this [`Symbol`] or [`CustomSymbol`] into a human-readable [`SymbolName`],
+    /// which is stored entirely on the stack and can be used as a [`str`].
+    pub fn inline_name(&self) -> SymbolName<N, A, R> {
+        let mut name: SymbolName<N, A, R> = SymbolName {
+            buf: NameBuffer::EMPTY,
+            len: 0,
+        };
+        for c in self.chars() {
+            name.len += c.encode_utf8(&mut name.buf.as_mut()[name.len..]).len();
+        }
+        name
+    }
+
+    /// Re-encodes this [`Symbol`] / [`CustomSymbol`] as a [`CustomSymbol`] using the
+    /// [`Alphabet`] `B`, one digit at a time and without going through a [`String`].
+    ///
+    /// This is equivalent to parsing the name of this symbol using `B` (including its
+    /// [`Alphabet::NORMALIZATION`] rules), so it fails if any of the characters of this symbol
+    /// are not in `B`, or if it is longer than `B` allows. Conversions that can never fail are
+    /// also available as [`From`] impls, which [`custom_alphabet!`] provides automatically
+    /// between custom alphabets and the [`Alphabet`]s shipped with this crate.
+    pub fn convert<const M: usize, B: Alphabet<M>>(
+        self,
+    ) -> core::result::Result<CustomSymbol<M, B>, SymbolParsingError> {
+        let char_size = N as u128 + 1;
+        // the last character has to be pushed first, so the digits are reversed beforehand
         let mut rem = self.data;
-        let char_size = (A::ALPHABET.len() + 1) as u128;
-        let mut result = String::with_capacity(A::MAX_SYMBOL_LEN);
-        while rem != 0 {
-            let it = rem % char_size;
-            rem -= it;
-            rem /= char_size;
-            result.push(A::ALPHABET[it as usize - 1]);
+        let mut rev = R::ZERO;
+        let mut len = 0;
+        while !rem.is_zero() {
+            let digit = rem.pop_digit(char_size);
+            rev = rev
+                .push_digit(char_size, digit)
+                .ok_or(SymbolParsingError::Overflow)?;
+            len += 1;
         }
-        result
+        let max = CustomSymbol::<M, B>::MAX_SYMBOL_LEN;
+        if len > max {
+            return Err(SymbolParsingError::TooLong { len, max });
+        }
+        let mut data = <B::Repr as SymbolRepr>::ZERO;
+        let mut index = len;
+        while !rev.is_zero() {
+            index -= 1;
+            let ch = A::ALPHABET[rev.pop_digit(char_size) as usize - 1];
+            let digit =
+                B::invert_char(ch).map_err(|_| SymbolParsingError::InvalidChar { ch, index })?;
+            data = data
+                .push_digit(M as u128 + 1, digit)
+                .ok_or(SymbolParsingError::Overflow)?;
+        }
+        Ok(CustomSymbol::from_raw_unchecked(data))
+    }
+
+    /// Returns an iterator over the [`char`]s of this [`Symbol`] / [`CustomSymbol`], which
+    /// decodes them one base-`(N + 1)` digit at a time without allocating.
+    pub const fn chars(&self) -> SymbolChars<N, A, R> {
+        SymbolChars {
+            _alphabet: PhantomData,
+            rem: self.data,
+        }
+    }
+}
+
+/// Implements the parts of the [`CustomSymbol`] API that need to do arithmetic in `const`
+/// contexts, once for each concrete [`SymbolRepr`].
+macro_rules! impl_const_symbol_api {
+    ($($repr:ident),*) => {
+        $(
+            impl<const N: usize, A: Alphabet<N>> CustomSymbol<N, A, $repr> {
+                /// Attempts to interpret the provided [`char`]s as a valid [`Symbol`] /
+                /// [`CustomSymbol`] in a `const` context, following the same rules as the
+                /// [`TryFrom<&str>`] impl. This is what the [`s!`] macro and the `parse_chars`
+                /// function generated by [`custom_alphabet!`] use under the hood. As with
+                /// [`CustomSymbol::try_from_str`], prefer the [`TryFrom<&str>`] / [`FromStr`]
+                /// impls at runtime.
+                pub const fn try_from_chars(
+                    mut chars: &[char],
+                ) -> core::result::Result<Self, SymbolParsingError> {
+                    // indices in errors refer to the untrimmed input
+                    let mut offset = 0;
+                    if A::NORMALIZATION.trim {
+                        while let [first, rest @ ..] = chars {
+                            if !first.is_ascii_whitespace() {
+                                break;
+                            }
+                            chars = rest;
+                            offset += 1;
+                        }
+                        while let [rest @ .., last] = chars {
+                            if !last.is_ascii_whitespace() {
+                                break;
+                            }
+                            chars = rest;
+                        }
+                    }
+                    if chars.is_empty() {
+                        return Err(SymbolParsingError::Empty);
+                    }
+                    if chars.len() > Self::MAX_SYMBOL_LEN {
+                        return Err(SymbolParsingError::TooLong {
+                            len: chars.len(),
+                            max: Self::MAX_SYMBOL_LEN,
+                        });
+                    }
+                    let mut data = <$repr as SymbolRepr>::ZERO;
+                    let mut index = chars.len();
+                    while index > 0 {
+                        index -= 1;
+                        let c = chars[index];
+                        let i = match invert_char_const::<N, A>(c, true) {
+                            Some(i) => i,
+                            None => {
+                                return Err(SymbolParsingError::InvalidChar {
+                                    ch: c,
+                                    index: offset + index,
+                                })
+                            }
+                        };
+                        data = match ReprOps::<$repr>::push_digit(data, N as u128 + 1, i) {
+                            Some(data) => data,
+                            None => return Err(SymbolParsingError::Overflow),
+                        };
+                    }
+                    Ok(CustomSymbol::from_raw_unchecked(data))
+                }
+
+                /// Attempts to interpret the provided string as a valid [`Symbol`] /
+                /// [`CustomSymbol`] in a `const` context, following the same rules as the
+                /// [`TryFrom<&str>`] impl. Unlike the [`s!`] macro, this accepts any `&str`
+                /// expression, such as the output of [`concat!`] or [`env!`].
+                ///
+                /// Because trait methods cannot be called in `const` contexts, this looks up
+                /// characters by scanning [`Alphabet::ALPHABET`] rather than calling
+                /// [`Alphabet::invert_char`], so prefer the [`TryFrom<&str>`] / [`FromStr`]
+                /// impls at runtime.
+                pub const fn try_from_str(
+                    value: &str,
+                ) -> core::result::Result<Self, SymbolParsingError> {
+                    Self::parse_str(value, true)
+                }
+
+                /// Equivalent to [`CustomSymbol::try_from_str`] (including its caveat about
+                /// runtime use), but ignores the [`Alphabet::NORMALIZATION`] rules of the
+                /// [`Alphabet`], only accepting the exact characters of the [`Alphabet`].
+                pub const fn try_from_str_strict(
+                    value: &str,
+                ) -> core::result::Result<Self, SymbolParsingError> {
+                    Self::parse_str(value, false)
+                }
+
+                /// Implements [`CustomSymbol::try_from_str`] and
+                /// [`CustomSymbol::try_from_str_strict`] (see the former for why this scans
+                /// [`Alphabet::ALPHABET`]).
+                const fn parse_str(
+                    mut value: &str,
+                    normalize: bool,
+                ) -> core::result::Result<Self, SymbolParsingError> {
+                    // indices in errors refer to the untrimmed input
+                    let mut offset = 0;
+                    if normalize && A::NORMALIZATION.trim {
+                        let trimmed = value.trim_ascii_start();
+                        offset = value.len() - trimmed.len();
+                        value = trimmed.trim_ascii_end();
+                    }
+                    let bytes = value.as_bytes();
+                    if bytes.is_empty() {
+                        return Err(SymbolParsingError::Empty);
+                    }
+                    let mut len = 0;
+                    let mut pos = 0;
+                    while pos < bytes.len() {
+                        if !is_utf8_continuation(bytes[pos]) {
+                            len += 1;
+                        }
+                        pos += 1;
+                    }
+                    if len > Self::MAX_SYMBOL_LEN {
+                        return Err(SymbolParsingError::TooLong {
+                            len,
+                            max: Self::MAX_SYMBOL_LEN,
+                        });
+                    }
+                    let mut data = <$repr as SymbolRepr>::ZERO;
+                    let mut end = bytes.len();
+                    let mut index = len;
+                    while end > 0 {
+                        let mut start = end - 1;
+                        while is_utf8_continuation(bytes[start]) {
+                            start -= 1;
+                        }
+                        index -= 1;
+                        let c = decode_utf8_char(bytes, start, end);
+                        let i = match invert_char_const::<N, A>(c, normalize) {
+                            Some(i) => i,
+                            None => {
+                                return Err(SymbolParsingError::InvalidChar {
+                                    ch: c,
+                                    index: offset + index,
+                                })
+                            }
+                        };
+                        data = match ReprOps::<$repr>::push_digit(data, N as u128 + 1, i) {
+                            Some(data) => data,
+                            None => return Err(SymbolParsingError::Overflow),
+                        };
+                        end = start;
+                    }
+                    Ok(CustomSymbol::from_raw_unchecked(data))
+                }
+
+                /// Creates a [`Symbol`] / [`CustomSymbol`] from a raw backing integer, such as one
+                /// previously returned by [`CustomSymbol::to_raw`], in a `const` context. This
+                /// is the validating counterpart of [`CustomSymbol::from_raw_unchecked`], and
+                /// is also available via the corresponding [`TryFrom`] impl.
+                ///
+                /// Every base-`(N + 1)` digit of `data` up to the most significant non-zero one
+                /// must itself be non-zero (a zero digit does not correspond to any character),
+                /// and there must be at most [`CustomSymbol::MAX_SYMBOL_LEN`] of them, so any
+                /// symbol returned by this function can be decoded without panicking.
+                pub const fn try_from_raw(
+                    data: $repr,
+                ) -> core::result::Result<Self, SymbolParsingError> {
+                    let mut rem = data;
+                    let mut len = 0;
+                    while !ReprOps::<$repr>::is_zero(rem) {
+                        if ReprOps::<$repr>::pop_digit(&mut rem, N as u128 + 1) == 0 {
+                            return Err(SymbolParsingError::InvalidRaw);
+                        }
+                        len += 1;
+                    }
+                    if len == 0 {
+                        return Err(SymbolParsingError::Empty);
+                    }
+                    if len > Self::MAX_SYMBOL_LEN {
+                        return Err(SymbolParsingError::TooLong {
+                            len,
+                            max: Self::MAX_SYMBOL_LEN,
+                        });
+                    }
+                    Ok(CustomSymbol::from_raw_unchecked(data))
+                }
+
+                /// Returns the number of [`char`]s in this [`Symbol`] / [`CustomSymbol`], which
+                /// is at most [`CustomSymbol::MAX_SYMBOL_LEN`].
+                pub const fn len(&self) -> usize {
+                    let mut rem = self.data;
+                    let mut len = 0;
+                    while !ReprOps::<$repr>::is_zero(rem) {
+                        ReprOps::<$repr>::pop_digit(&mut rem, N as u128 + 1);
+                        len += 1;
+                    }
+                    len
+                }
+
+                /// Returns `true` if this [`Symbol`] / [`CustomSymbol`] contains no [`char`]s.
+                /// This can only be the case for symbols created via
+                /// [`CustomSymbol::from_raw_unchecked`], since every other way of creating a
+                /// symbol (including [`CustomSymbol::strip_prefix`] and friends) rejects empty
+                /// symbols.
+                pub const fn is_empty(&self) -> bool {
+                    ReprOps::<$repr>::is_zero(self.data)
+                }
+
+                /// Returns the [`char`] at the specified (0-based) index of this [`Symbol`] /
+                /// [`CustomSymbol`], or [`None`] if `index` is out of bounds. Like indexing a
+                /// [`str`] by [`char`]s, this takes time proportional to `index`.
+                pub const fn char_at(&self, index: usize) -> Option<char> {
+                    let mut rem = self.data;
+                    let mut i = 0;
+                    while i < index {
+                        if ReprOps::<$repr>::is_zero(rem) {
+                            return None;
+                        }
+                        ReprOps::<$repr>::pop_digit(&mut rem, N as u128 + 1);
+                        i += 1;
+                    }
+                    match ReprOps::<$repr>::pop_digit(&mut rem, N as u128 + 1) {
+                        0 => None,
+                        digit => Some(A::ALPHABET[digit as usize - 1]),
+                    }
+                }
+
+                /// Returns the first [`char`] of this [`Symbol`] / [`CustomSymbol`], or [`None`]
+                /// if it is empty.
+                pub const fn first(&self) -> Option<char> {
+                    self.char_at(0)
+                }
+
+                /// Returns the last [`char`] of this [`Symbol`] / [`CustomSymbol`], or [`None`]
+                /// if it is empty.
+                pub const fn last(&self) -> Option<char> {
+                    let mut rem = self.data;
+                    let mut last = None;
+                    while !ReprOps::<$repr>::is_zero(rem) {
+                        let digit = ReprOps::<$repr>::pop_digit(&mut rem, N as u128 + 1);
+                        last = Some(A::ALPHABET[digit as usize - 1]);
+                    }
+                    last
+                }
+
+                /// Returns the [`Symbol`] / [`CustomSymbol`] consisting of the characters of
+                /// `self` followed by the characters of `other`, or
+                /// [`SymbolParsingError::TooLong`] if the result would be longer than
+                /// [`CustomSymbol::MAX_SYMBOL_LEN`]. This is pure arithmetic on the backing
+                /// integers, so no decoding or re-parsing is involved.
+                ///
+                /// See the [`s!`] macro for concatenating symbols at compile time.
+                pub const fn concat(
+                    self,
+                    other: Self,
+                ) -> core::result::Result<Self, SymbolParsingError> {
+                    let len = self.len() + other.len();
+                    if len > Self::MAX_SYMBOL_LEN {
+                        return Err(SymbolParsingError::TooLong {
+                            len,
+                            max: Self::MAX_SYMBOL_LEN,
+                        });
+                    }
+                    // the first character is the least significant digit, so the digits of
+                    // `self` are pushed below those of `other`, last character first
+                    let mut rev = Self::reverse_digits(self.data, usize::MAX);
+                    let mut data = other.data;
+                    while !ReprOps::<$repr>::is_zero(rev) {
+                        let digit = ReprOps::<$repr>::pop_digit(&mut rev, N as u128 + 1);
+                        data = match ReprOps::<$repr>::push_digit(data, N as u128 + 1, digit) {
+                            Some(data) => data,
+                            None => return Err(SymbolParsingError::Overflow),
+                        };
+                    }
+                    Ok(CustomSymbol::from_raw_unchecked(data))
+                }
+
+                /// Returns this [`Symbol`] / [`CustomSymbol`] with the specified [`char`]
+                /// appended, following the [`Alphabet::NORMALIZATION`] rules of the
+                /// [`Alphabet`]. Fails with [`SymbolParsingError::InvalidChar`] if `c` is not
+                /// in the [`Alphabet`], or [`SymbolParsingError::TooLong`] if the result would
+                /// be longer than [`CustomSymbol::MAX_SYMBOL_LEN`].
+                pub const fn push_char(
+                    self,
+                    c: char,
+                ) -> core::result::Result<Self, SymbolParsingError> {
+                    let digit = match invert_char_const::<N, A>(c, true) {
+                        Some(digit) => digit,
+                        None => {
+                            return Err(SymbolParsingError::InvalidChar {
+                                ch: c,
+                                index: self.len(),
+                            })
+                        }
+                    };
+                    match ReprOps::<$repr>::push_digit(
+                        <$repr as SymbolRepr>::ZERO,
+                        N as u128 + 1,
+                        digit,
+                    ) {
+                        Some(data) => self.concat(CustomSymbol::from_raw_unchecked(data)),
+                        None => Err(SymbolParsingError::Overflow),
+                    }
+                }
+
+                /// Returns `true` if the characters of `prefix` are the first characters of
+                /// this [`Symbol`] / [`CustomSymbol`].
+                pub const fn starts_with(&self, prefix: Self) -> bool {
+                    let mut rem = self.data;
+                    let mut prefix = prefix.data;
+                    while !ReprOps::<$repr>::is_zero(prefix) {
+                        if ReprOps::<$repr>::pop_digit(&mut rem, N as u128 + 1)
+                            != ReprOps::<$repr>::pop_digit(&mut prefix, N as u128 + 1)
+                        {
+                            return false;
+                        }
+                    }
+                    true
+                }
+
+                /// Returns `true` if the characters of `suffix` are the last characters of
+                /// this [`Symbol`] / [`CustomSymbol`].
+                pub const fn ends_with(&self, suffix: Self) -> bool {
+                    let (len, suffix_len) = (self.len(), suffix.len());
+                    if suffix_len > len {
+                        return false;
+                    }
+                    Self::digits_eq(Self::skip_digits(self.data, len - suffix_len), suffix.data)
+                }
+
+                /// Returns this [`Symbol`] / [`CustomSymbol`] with `prefix` removed from the
+                /// start, or [`None`] if it doesn't start with `prefix`. Since symbols can't
+                /// be empty, this also returns [`None`] if `prefix` is the whole symbol
+                /// (unlike [`str::strip_prefix`]).
+                pub const fn strip_prefix(self, prefix: Self) -> Option<Self> {
+                    if !self.starts_with(prefix) || prefix.len() == self.len() {
+                        return None;
+                    }
+                    Some(CustomSymbol::from_raw_unchecked(Self::skip_digits(
+                        self.data,
+                        prefix.len(),
+                    )))
+                }
+
+                /// Returns this [`Symbol`] / [`CustomSymbol`] with `suffix` removed from the
+                /// end, or [`None`] if it doesn't end with `suffix`. Since symbols can't be
+                /// empty, this also returns [`None`] if `suffix` is the whole symbol (unlike
+                /// [`str::strip_suffix`]).
+                pub const fn strip_suffix(self, suffix: Self) -> Option<Self> {
+                    if !self.ends_with(suffix) || suffix.len() == self.len() {
+                        return None;
+                    }
+                    Some(CustomSymbol::from_raw_unchecked(Self::take_digits(
+                        self.data,
+                        self.len() - suffix.len(),
+                    )))
+                }
+
+                /// Returns the [`Symbol`] / [`CustomSymbol`] consisting of the characters in
+                /// the specified (0-based, character-wise) range, or [`None`] if the range is
+                /// out of bounds or empty (since symbols can't be empty).
+                pub const fn slice(self, range: core::ops::Range<usize>) -> Option<Self> {
+                    if range.start >= range.end || range.end > self.len() {
+                        return None;
+                    }
+                    let rest = Self::skip_digits(self.data, range.start);
+                    Some(CustomSymbol::from_raw_unchecked(Self::take_digits(
+                        rest,
+                        range.end - range.start,
+                    )))
+                }
+
+                /// Splits this [`Symbol`] / [`CustomSymbol`] into its first `mid` characters
+                /// and the remaining characters, or returns [`None`] if either half would be
+                /// empty (since symbols can't be empty), i.e. unless `0 < mid < self.len()`.
+                pub const fn split_at(self, mid: usize) -> Option<(Self, Self)> {
+                    if mid == 0 || mid >= self.len() {
+                        return None;
+                    }
+                    Some((
+                        CustomSymbol::from_raw_unchecked(Self::take_digits(self.data, mid)),
+                        CustomSymbol::from_raw_unchecked(Self::skip_digits(self.data, mid)),
+                    ))
+                }
+
+                /// Removes the first (i.e. least significant) `count` digits of `data`.
+                const fn skip_digits(mut data: $repr, count: usize) -> $repr {
+                    let mut i = 0;
+                    while i < count && !ReprOps::<$repr>::is_zero(data) {
+                        ReprOps::<$repr>::pop_digit(&mut data, N as u128 + 1);
+                        i += 1;
+                    }
+                    data
+                }
+
+                /// Keeps only the first (i.e. least significant) `count` digits of `data`.
+                const fn take_digits(data: $repr, count: usize) -> $repr {
+                    let rev = Self::reverse_digits(data, count);
+                    Self::reverse_digits(rev, usize::MAX)
+                }
+
+                /// Returns the first (i.e. least significant) `count` digits of `data` in
+                /// reverse order. Since every digit of a symbol is non-zero, reversing never
+                /// loses any digits and never needs more space than `data` itself.
+                const fn reverse_digits(mut data: $repr, count: usize) -> $repr {
+                    let mut rev = <$repr as SymbolRepr>::ZERO;
+                    let mut i = 0;
+                    while i < count && !ReprOps::<$repr>::is_zero(data) {
+                        let digit = ReprOps::<$repr>::pop_digit(&mut data, N as u128 + 1);
+                        rev = match ReprOps::<$repr>::push_digit(rev, N as u128 + 1, digit) {
+                            Some(rev) => rev,
+                            None => unreachable!(),
+                        };
+                        i += 1;
+                    }
+                    rev
+                }
+
+                /// Compares `a` and `b` digit by digit, since [`PartialEq`] can't be used in
+                /// `const` contexts for every [`SymbolRepr`].
+                const fn digits_eq(mut a: $repr, mut b: $repr) -> bool {
+                    while !ReprOps::<$repr>::is_zero(a) || !ReprOps::<$repr>::is_zero(b) {
+                        if ReprOps::<$repr>::pop_digit(&mut a, N as u128 + 1)
+                            != ReprOps::<$repr>::pop_digit(&mut b, N as u128 + 1)
+                        {
+                            return false;
+                        }
+                    }
+                    true
+                }
+
+                /// Used internally by the [`symbol_map!`] and [`symbol_set!`] macros to hash
+                /// symbols while constructing a perfect hash function at const-eval time.
+                #[doc(hidden)]
+                pub const fn __words(self) -> [u64; 4] {
+                    ReprOps::<$repr>::words(self.data)
+                }
+            }
+
+            impl<const N: usize, A: Alphabet<N>> From<CustomSymbol<N, A, $repr>> for $repr {
+                fn from(value: CustomSymbol<N, A, $repr>) -> Self {
+                    value.data
+                }
+            }
+
+            impl<const N: usize, A: Alphabet<N>> TryFrom<$repr> for CustomSymbol<N, A, $repr> {
+                type Error = SymbolParsingError;
+
+                /// Equivalent to [`CustomSymbol::try_from_raw`].
+                fn try_from(value: $repr) -> core::result::Result<Self, Self::Error> {
+                    Self::try_from_raw(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_const_symbol_api!(u32, u64, u128, U256);
+
+/// An iterator over the [`char`]s of a [`CustomSymbol`], as returned by
+/// [`CustomSymbol::chars`].
+#[derive(Copy, Clone)]
+pub struct SymbolChars<const N: usize, A: Alphabet<N>, R: SymbolRepr> {
+    _alphabet: PhantomData<A>,
+    rem: R,
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Iterator for SymbolChars<N, A, R> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if self.rem.is_zero() {
+            return None;
+        }
+        let digit = self.rem.pop_digit(N as u128 + 1);
+        Some(A::ALPHABET[digit as usize - 1])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.rem.is_zero() {
+            (0, Some(0))
+        } else {
+            (1, Some(CustomSymbol::<N, A, R>::MAX_SYMBOL_LEN))
+        }
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> core::iter::FusedIterator
+    for SymbolChars<N, A, R>
+{
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Debug for SymbolChars<N, A, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
+
+/// A stack-allocated, fixed-capacity string containing the name of a [`CustomSymbol`], as
+/// returned by [`CustomSymbol::inline_name`]. Dereferences to [`str`].
+#[derive(Copy, Clone)]
+pub struct SymbolName<const N: usize, A: Alphabet<N>, R: SymbolRepr = <A as Alphabet<N>>::Repr> {
+    buf: R::NameBuffer<N, A>,
+    len: usize,
+}
+
+/// A fixed-size byte array used as the backing for a [`SymbolName`] (see
+/// [`SymbolRepr::NameBuffer`]).
+#[doc(hidden)]
+pub trait NameBuffer: Copy + AsRef<[u8]> + AsMut<[u8]> {
+    /// An empty (zeroed) [`NameBuffer`].
+    const EMPTY: Self;
+}
+
+impl<const LEN: usize> NameBuffer for [u8; LEN] {
+    const EMPTY: Self = [0; LEN];
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> SymbolName<N, A, R> {
+    /// Returns the contents of this [`SymbolName`] as a [`str`].
+    pub fn as_str(&self) -> &str {
+        // SAFETY: `buf[..len]` is only ever written to by `CustomSymbol::inline_name`, which
+        // fills it with whole UTF-8 encoded `char`s.
+        unsafe { core::str::from_utf8_unchecked(&self.buf.as_ref()[..self.len]) }
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Deref for SymbolName<N, A, R> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> AsRef<str> for SymbolName<N, A, R> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> PartialEq for SymbolName<N, A, R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Eq for SymbolName<N, A, R> {}
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> PartialEq<str> for SymbolName<N, A, R> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> PartialEq<&str> for SymbolName<N, A, R> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Debug for SymbolName<N, A, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Display for SymbolName<N, A, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.write_str(self.as_str())
     }
 }
 
-impl<const N: usize, A: Alphabet<N>> PartialEq for CustomSymbol<N, A> {
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> PartialEq for CustomSymbol<N, A, R> {
     fn eq(&self, other: &Self) -> bool {
         self.data == other.data
     }
 }
-impl<const N: usize, A: Alphabet<N>> Eq for CustomSymbol<N, A> {}
-impl<const N: usize, A: Alphabet<N>> Hash for CustomSymbol<N, A> {
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Eq for CustomSymbol<N, A, R> {}
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Hash for CustomSymbol<N, A, R> {
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.data.hash(state);
     }
 }
-impl<const N: usize, A: Alphabet<N>> PartialOrd for CustomSymbol<N, A> {
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> PartialOrd for CustomSymbol<N, A, R> {
     fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
-impl<const N: usize, A: Alphabet<N>> Ord for CustomSymbol<N, A> {
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Ord for CustomSymbol<N, A, R> {
     fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.data.cmp(&other.data)
     }
 }
 
-impl<const N: usize, A: Alphabet<N>> From<CustomSymbol<N, A>> for u128 {
-    fn from(value: CustomSymbol<N, A>) -> Self {
-        value.data
-    }
-}
-
 /// Thrown when an attempt was made to parse an invalid [`CustomSymbol`] / [`Symbol`]. This can
 /// occur when the underlying ident or string is too long, too short, or contains invalid
 /// character (characters not in the specified [`Alphabet`]).
-pub struct SymbolParsingError;
+///
+/// The [`Display`] impl provides a detailed description of what went wrong (including the
+/// offending character and its position, if applicable), while [`SymbolParsingError::message`]
+/// provides a shorter `const`-friendly description of the kind of error.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SymbolParsingError {
+    /// The provided ident or string contained no characters.
+    Empty,
+    /// The provided ident or string contained `len` characters, but the [`Alphabet`] and
+    /// [`SymbolRepr`] only allow for `max` characters (i.e. `CustomSymbol::MAX_SYMBOL_LEN`).
+    TooLong { len: usize, max: usize },
+    /// The character `ch` at (0-based, character-wise) position `index` is not contained in
+    /// the [`Alphabet`]. When returned directly by [`Alphabet::invert_char`], `index` is
+    /// always `0`, since the position of the character is not known.
+    InvalidChar { ch: char, index: usize },
+    /// The encoded symbol would not fit in the backing [`SymbolRepr`].
+    Overflow,
+    /// The raw [`SymbolRepr`] passed to [`CustomSymbol::try_from_raw`] contains a zero digit
+    /// below its most significant digit, which does not correspond to any character (or the
+    /// [`u128`] passed to [`CustomLexSymbol::try_from_raw`] isn't correctly left-aligned).
+    InvalidRaw,
+}
+
+impl SymbolParsingError {
+    /// Returns a short, static description of this kind of [`SymbolParsingError`]. Unlike the
+    /// [`Display`] impl, this can be used in `const` contexts, such as when panicking during
+    /// const-eval.
+    pub const fn message(&self) -> &'static str {
+        match self {
+            SymbolParsingError::Empty => "a `Symbol` or `CustomSymbol` cannot be empty",
+            SymbolParsingError::TooLong { .. } => {
+                "a `Symbol` or `CustomSymbol` cannot be longer than `Alphabet::MAX_SYMBOL_LEN` \
+                characters"
+            }
+            SymbolParsingError::InvalidChar { .. } => {
+                "a `Symbol` or `CustomSymbol` can only contain characters that are included in \
+                its `Alphabet`"
+            }
+            SymbolParsingError::Overflow => {
+                "a `Symbol` or `CustomSymbol` must fit within its backing `SymbolRepr`"
+            }
+            SymbolParsingError::InvalidRaw => {
+                "the raw value of a `Symbol` or `CustomSymbol` must consist only of digits that \
+                correspond to characters in its `Alphabet`"
+            }
+        }
+    }
+}
 
+/// A generic description of the rules for a valid [`CustomSymbol`] / [`Symbol`]. See
+/// [`SymbolParsingError`] for details on specific failures.
 pub const PARSING_ERROR_MSG: &str =
     "To be a valid `Symbol` or `CustomSymbol`, the provided ident or string must be at least one \
     character long, at most `Alphabet::MAX_SYMBOL_LEN` characters long, and consist only of \
     characters that are included in the `Alphabet`. No other characters are permitted, nor is \
     whitespace of any kind.";
 
-impl Debug for SymbolParsingError {
+impl Display for SymbolParsingError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        f.write_str(PARSING_ERROR_MSG)
+        match self {
+            SymbolParsingError::Empty => f.write_str(self.message()),
+            SymbolParsingError::TooLong { len, max } => write!(
+                f,
+                "symbol is {len} characters long, but its `Alphabet` allows at most {max}"
+            ),
+            SymbolParsingError::InvalidChar { ch, index } => write!(
+                f,
+                "invalid character {ch:?} at position {index}, which is not in the `Alphabet`"
+            ),
+            SymbolParsingError::Overflow | SymbolParsingError::InvalidRaw => {
+                f.write_str(self.message())
+            }
+        }
     }
 }
 
-impl<const N: usize, A: Alphabet<N>> TryFrom<&str> for CustomSymbol<N, A> {
+impl core::error::Error for SymbolParsingError {}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> TryFrom<&str> for CustomSymbol<N, A, R> {
     type Error = SymbolParsingError;
 
     /// Attempts to interpret the provided string as a valid [`Symbol`] / [`CustomSymbol`]. The usual parsing
     /// rules for [`CustomSymbol`] apply, namely:
     /// - At least one character
-    /// - At most `Alphabet::MAX_SYMBOL_LEN` characters
+    /// - At most `CustomSymbol::MAX_SYMBOL_LEN` characters (counted as [`char`]s, not bytes)
     /// - Only characters that are contained in the [`Alphabet`].
     ///
-    /// If any of these requirements are violated, a generic [`SymbolParsingError`] is returned
-    /// and parsing will abort.
-    fn try_from(value: &str) -> core::result::Result<Self, Self::Error> {
-        if value.is_empty() || value.len() > A::MAX_SYMBOL_LEN {
-            return Err(SymbolParsingError {});
+    /// The input is first normalized according to the [`Alphabet::NORMALIZATION`] rules of
+    /// the [`Alphabet`] (see [`CustomSymbol::try_from_str_strict`] for a version that
+    /// doesn't). If any of these requirements are violated, a [`SymbolParsingError`]
+    /// describing the problem is returned and parsing will abort.
+    fn try_from(mut value: &str) -> core::result::Result<Self, Self::Error> {
+        // indices in errors refer to the untrimmed input
+        let mut offset = 0;
+        if A::NORMALIZATION.trim {
+            let trimmed = value.trim_ascii_start();
+            offset = value.len() - trimmed.len();
+            value = trimmed.trim_ascii_end();
+        }
+        if value.is_empty() {
+            return Err(SymbolParsingError::Empty);
+        }
+        // lengths are measured in `char`s rather than bytes, matching the `s!` macro
+        let len = value.chars().count();
+        if len > Self::MAX_SYMBOL_LEN {
+            return Err(SymbolParsingError::TooLong {
+                len,
+                max: Self::MAX_SYMBOL_LEN,
+            });
         }
-        let mut data: u128 = 0;
-        for c in value.chars().rev() {
-            data *= A::LEN_U218 + 1;
-            data += A::invert_char(c)?;
+        let mut data = R::ZERO;
+        for (rev_index, c) in value.chars().rev().enumerate() {
+            let i = match A::invert_char(c) {
+                Ok(i) => i,
+                Err(_) => {
+                    return Err(SymbolParsingError::InvalidChar {
+                        ch: c,
+                        index: offset + len - 1 - rev_index,
+                    })
+                }
+            };
+            data = data
+                .push_digit(A::LEN_U218 + 1, i)
+                .ok_or(SymbolParsingError::Overflow)?;
         }
         Ok(CustomSymbol {
             _alphabet: PhantomData,
@@ -204,7 +1214,17 @@ impl<const N: usize, A: Alphabet<N>> TryFrom<&str> for CustomSymbol<N, A> {
     }
 }
 
-impl<const N: usize, A: Alphabet<N>> TryFrom<String> for CustomSymbol<N, A> {
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> FromStr for CustomSymbol<N, A, R> {
+    type Err = SymbolParsingError;
+
+    /// Equivalent to the [`TryFrom<&str>`] impl, allowing for `"hello".parse::<Symbol>()`.
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        CustomSymbol::try_from(s)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> TryFrom<String> for CustomSymbol<N, A, R> {
     type Error = SymbolParsingError;
 
     fn try_from(value: String) -> core::result::Result<Self, Self::Error> {
@@ -212,7 +1232,8 @@ impl<const N: usize, A: Alphabet<N>> TryFrom<String> for CustomSymbol<N, A> {
     }
 }
 
-impl<const N: usize, A: Alphabet<N>> TryFrom<&String> for CustomSymbol<N, A> {
+#[cfg(feature = "alloc")]
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> TryFrom<&String> for CustomSymbol<N, A, R> {
     type Error = SymbolParsingError;
 
     fn try_from(value: &String) -> core::result::Result<Self, Self::Error> {
@@ -220,42 +1241,177 @@ impl<const N: usize, A: Alphabet<N>> TryFrom<&String> for CustomSymbol<N, A> {
     }
 }
 
-impl<const N: usize, A: Alphabet<N>> From<CustomSymbol<N, A>> for String {
-    fn from(value: CustomSymbol<N, A>) -> Self {
+#[cfg(feature = "alloc")]
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> From<CustomSymbol<N, A, R>> for String {
+    fn from(value: CustomSymbol<N, A, R>) -> Self {
         value.name()
     }
 }
 
-impl<const N: usize, A: Alphabet<N>> From<&CustomSymbol<N, A>> for String {
-    fn from(value: &CustomSymbol<N, A>) -> Self {
+#[cfg(feature = "alloc")]
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> From<&CustomSymbol<N, A, R>> for String {
+    fn from(value: &CustomSymbol<N, A, R>) -> Self {
         (*value).into()
     }
 }
 
-impl<const N: usize, A: Alphabet<N>> Debug for CustomSymbol<N, A> {
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Debug for CustomSymbol<N, A, R> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         f.debug_struct("Symbol")
             .field("data", &self.data)
-            .field("symbol", &String::from(*self))
+            .field("symbol", &self.inline_name())
             .finish()
     }
 }
 
-impl<const N: usize, A: Alphabet<N>> Display for CustomSymbol<N, A> {
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> Display for CustomSymbol<N, A, R> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let value: String = self.name();
-        f.write_str(&value)
+        for c in self.chars() {
+            f.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+/// Internal function used to determine whether a raw [`SymbolRepr`] decodes to a valid
+/// [`CustomSymbol`] in the specified [`Alphabet`], i.e. every base-`(N + 1)` digit up to the
+/// most significant one is non-zero and there are at most `CustomSymbol::MAX_SYMBOL_LEN` of
+/// them.
+fn raw_is_valid<const N: usize, A: Alphabet<N>, R: SymbolRepr>(data: R) -> bool {
+    let char_size = N as u128 + 1;
+    let mut rem = data;
+    let mut len = 0;
+    while !rem.is_zero() {
+        if rem.pop_digit(char_size) == 0 {
+            return false;
+        }
+        len += 1;
     }
+    len > 0 && len <= CustomSymbol::<N, A, R>::MAX_SYMBOL_LEN
 }
 
-/// Internal function used to calculate the `ceil(log2(x))` when determining the
-/// `MAX_SYMBOL_LEN` of an [`Alphabet`].
-const fn ceil_log2(x: usize) -> usize {
-    let mut n = x;
-    let mut log = 0;
-    while n > 1 {
-        n = (n + 1) / 2; // ceil division
-        log += 1;
+/// Used internally by the macros in this crate to determine whether the symbol at `index`
+/// (given as the words returned by `CustomSymbol::__words`) is equal to any of the symbols
+/// at the specified `others` indices, so that duplicate symbols can be reported in `const`
+/// contexts when the [`Alphabet`] of the symbols isn't known to the macros.
+#[doc(hidden)]
+pub const fn __is_duplicate(words: &[[u64; 4]], others: &[usize], index: usize) -> bool {
+    let mut i = 0;
+    while i < others.len() {
+        let other = words[others[i]];
+        if other[0] == words[index][0]
+            && other[1] == words[index][1]
+            && other[2] == words[index][2]
+            && other[3] == words[index][3]
+        {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Internal function used by [`__check_known_alphabets!`] to determine whether the
+/// [`Alphabet`] `A` consists of exactly the specified characters (in order), and accepts
+/// exactly the specified `(alias, char)` pairs in place of them.
+const fn matches_known_alphabet<const N: usize, A: Alphabet<N>>(
+    chars: &[char],
+    aliases: &[(char, char)],
+) -> bool {
+    if chars.len() != N || A::NORMALIZATION.trim || A::NORMALIZATION.aliases.len() != aliases.len()
+    {
+        return false;
+    }
+    let mut i = 0;
+    while i < N {
+        if A::ALPHABET[i] != chars[i] {
+            return false;
+        }
+        i += 1;
+    }
+    let mut i = 0;
+    while i < aliases.len() {
+        let (Some(alias), Some(target)) = (
+            invert_char_const::<N, A>(aliases[i].0, true),
+            invert_char_const::<N, A>(aliases[i].1, false),
+        ) else {
+            return false;
+        };
+        if alias != target {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Internal function used to look up the 1-based index of a [`char`] in an [`Alphabet`] in
+/// `const` contexts, where [`Alphabet::invert_char`] cannot be called. If `normalize` is
+/// `true`, the [`Normalization::aliases`] of the [`Alphabet`] are also considered.
+const fn invert_char_const<const N: usize, A: Alphabet<N>>(
+    c: char,
+    normalize: bool,
+) -> Option<u128> {
+    let mut i = 0;
+    while i < N {
+        if A::ALPHABET[i] == c {
+            return Some(i as u128 + 1);
+        }
+        i += 1;
+    }
+    if normalize {
+        let aliases = A::NORMALIZATION.aliases;
+        let mut i = 0;
+        while i < aliases.len() {
+            if aliases[i].0 == c {
+                return invert_char_const::<N, A>(aliases[i].1, false);
+            }
+            i += 1;
+        }
+    }
+    None
+}
+
+/// Internal function used to determine whether a byte is a UTF-8 continuation byte, i.e. not
+/// the first byte of a [`char`].
+const fn is_utf8_continuation(byte: u8) -> bool {
+    byte & 0b1100_0000 == 0b1000_0000
+}
+
+/// Internal function used to decode the [`char`] stored in `bytes[start..end]`, which must be
+/// a single valid UTF-8 encoded [`char`] (i.e. from a [`str`]).
+const fn decode_utf8_char(bytes: &[u8], start: usize, end: usize) -> char {
+    let mut code = match end - start {
+        1 => bytes[start] as u32,
+        2 => (bytes[start] & 0b0001_1111) as u32,
+        3 => (bytes[start] & 0b0000_1111) as u32,
+        _ => (bytes[start] & 0b0000_0111) as u32,
+    };
+    let mut pos = start + 1;
+    while pos < end {
+        code = (code << 6) | (bytes[pos] & 0b0011_1111) as u32;
+        pos += 1;
+    }
+    match char::from_u32(code) {
+        Some(c) => c,
+        None => char::REPLACEMENT_CHARACTER,
+    }
+}
+
+/// Internal function used to calculate the `MAX_SYMBOL_LEN` of an [`Alphabet`], i.e. the
+/// largest number of base-`char_size` digits that can always be stored in `bits` bits.
+const fn max_symbol_len(char_size: u128, bits: u32) -> usize {
+    if char_size < 2 {
+        return 0;
+    }
+    // `max` is the largest value representable using `len` digits, i.e. `char_size^len - 1`
+    let mut max = U256::ZERO;
+    let mut len = 0;
+    loop {
+        max = match max.checked_mul_add(char_size as u64, char_size as u64 - 1) {
+            Some(max) if max.bit_len() <= bits => max,
+            _ => return len,
+        };
+        len += 1;
     }
-    log
 }
@@ -0,0 +1,357 @@
+//! Contains [`SymbolMap`] and [`SymbolSet`], static lookup tables keyed by symbols that use a
+//! perfect hash function constructed at compile time by the [`symbol_map!`] and
+//! [`symbol_set!`] macros.
+
+use super::*;
+
+/// An immutable map from symbols to values, constructed at compile time using the
+/// [`symbol_map!`] macro and typically stored in a `static` or `const`.
+///
+/// Lookups use a perfect hash function that is found during const-eval (using the
+/// "hash, displace and compress" algorithm), so retrieving a value only requires mixing the
+/// bits of the symbol, two array lookups and a single comparison, with no allocation and no
+/// probing. Iteration visits entries in the order they were declared.
+///
+/// The key type `S` defaults to [`Symbol`], but any [`CustomSymbol`] can be used.
+///
+/// ### Example
+#[doc = docify::embed_run!("tests/tests.rs", symbol_map_example)]
+pub struct SymbolMap<V: 'static, S: 'static = Symbol> {
+    keys: &'static [S],
+    values: &'static [V],
+    seed: u64,
+    displacements: &'static [(u32, u32)],
+    slots: &'static [usize],
+}
+
+impl<V: 'static, S: 'static> SymbolMap<V, S> {
+    /// Used internally by the [`symbol_map!`] macro to assemble a [`SymbolMap`] from its
+    /// parts, where `keys` and `values` are in declaration order.
+    #[doc(hidden)]
+    pub const fn __new(
+        keys: &'static [S],
+        values: &'static [V],
+        seed: u64,
+        displacements: &'static [(u32, u32)],
+        slots: &'static [usize],
+    ) -> Self {
+        SymbolMap {
+            keys,
+            values,
+            seed,
+            displacements,
+            slots,
+        }
+    }
+
+    /// Returns the number of entries in this [`SymbolMap`].
+    pub const fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if this [`SymbolMap`] contains no entries.
+    pub const fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Iterates over the keys of this [`SymbolMap`], in declaration order.
+    pub fn keys(&self) -> core::slice::Iter<'static, S> {
+        self.keys.iter()
+    }
+
+    /// Iterates over the values of this [`SymbolMap`], in declaration order.
+    pub fn values(&self) -> core::slice::Iter<'static, V> {
+        self.values.iter()
+    }
+
+    /// Iterates over the entries of this [`SymbolMap`], in declaration order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static S, &'static V)> {
+        self.keys.iter().zip(self.values.iter())
+    }
+}
+
+impl<V: 'static, const N: usize, A: Alphabet<N>, R: SymbolRepr>
+    SymbolMap<V, CustomSymbol<N, A, R>>
+{
+    /// Returns the value corresponding to the specified symbol, if any.
+    pub fn get(&self, key: &CustomSymbol<N, A, R>) -> Option<&'static V> {
+        self.index_of(key).map(|i| &self.values[i])
+    }
+
+    /// Returns the stored key and value corresponding to the specified symbol, if any.
+    pub fn get_key_value(
+        &self,
+        key: &CustomSymbol<N, A, R>,
+    ) -> Option<(&'static CustomSymbol<N, A, R>, &'static V)> {
+        self.index_of(key).map(|i| (&self.keys[i], &self.values[i]))
+    }
+
+    /// Returns `true` if this [`SymbolMap`] contains the specified symbol.
+    pub fn contains_key(&self, key: &CustomSymbol<N, A, R>) -> bool {
+        self.index_of(key).is_some()
+    }
+
+    /// Finds the (declaration order) index of the entry for the specified symbol, if any.
+    fn index_of(&self, key: &CustomSymbol<N, A, R>) -> Option<usize> {
+        if self.slots.is_empty() {
+            return None;
+        }
+        let (g, f1, f2) = perfect_hash(key.to_raw().words(), self.seed);
+        let (d1, d2) = self.displacements[g as usize % self.displacements.len()];
+        let i = self.slots[displace(f1, f2, d1, d2) as usize % self.slots.len()];
+        (self.keys[i] == *key).then_some(i)
+    }
+}
+
+impl<V: 'static, S: 'static> Copy for SymbolMap<V, S> {}
+impl<V: 'static, S: 'static> Clone for SymbolMap<V, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V: Debug + 'static, S: Debug + 'static> Debug for SymbolMap<V, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V: 'static, S: 'static> IntoIterator for &SymbolMap<V, S> {
+    type Item = (&'static S, &'static V);
+    type IntoIter = core::iter::Zip<core::slice::Iter<'static, S>, core::slice::Iter<'static, V>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.keys.iter().zip(self.values.iter())
+    }
+}
+
+/// An immutable set of symbols, constructed at compile time using the [`symbol_set!`] macro
+/// and typically stored in a `static` or `const`. This is the set counterpart of
+/// [`SymbolMap`], and uses the same perfect hashing scheme.
+pub struct SymbolSet<S: 'static = Symbol> {
+    map: SymbolMap<(), S>,
+}
+
+impl<S: 'static> SymbolSet<S> {
+    /// Used internally by the [`symbol_set!`] macro to assemble a [`SymbolSet`] from its
+    /// parts.
+    #[doc(hidden)]
+    pub const fn __new(map: SymbolMap<(), S>) -> Self {
+        SymbolSet { map }
+    }
+
+    /// Returns the number of symbols in this [`SymbolSet`].
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if this [`SymbolSet`] contains no symbols.
+    pub const fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Iterates over the symbols in this [`SymbolSet`], in declaration order.
+    pub fn iter(&self) -> core::slice::Iter<'static, S> {
+        self.map.keys()
+    }
+}
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> SymbolSet<CustomSymbol<N, A, R>> {
+    /// Returns `true` if this [`SymbolSet`] contains the specified symbol.
+    pub fn contains(&self, symbol: &CustomSymbol<N, A, R>) -> bool {
+        self.map.contains_key(symbol)
+    }
+}
+
+impl<S: 'static> Copy for SymbolSet<S> {}
+impl<S: 'static> Clone for SymbolSet<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: Debug + 'static> Debug for SymbolSet<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<S: 'static> IntoIterator for &SymbolSet<S> {
+    type Item = &'static S;
+    type IntoIter = core::slice::Iter<'static, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The result of constructing a perfect hash function for `LEN` keys using `BUCKETS`
+/// buckets at const-eval time. Used internally by the [`symbol_map!`] and [`symbol_set!`]
+/// macros.
+#[doc(hidden)]
+pub struct __PerfectHash<const LEN: usize, const BUCKETS: usize> {
+    pub seed: u64,
+    pub displacements: [(u32, u32); BUCKETS],
+    pub slots: [usize; LEN],
+}
+
+impl<const LEN: usize, const BUCKETS: usize> __PerfectHash<LEN, BUCKETS> {
+    /// Finds a perfect hash function for the specified keys (as returned by
+    /// `SymbolRepr::words`), panicking if the keys contain duplicates.
+    pub const fn new(keys: &[[u64; 4]; LEN]) -> Self {
+        let mut i = 0;
+        while i < LEN {
+            let mut j = i + 1;
+            while j < LEN {
+                if words_eq(keys[i], keys[j]) {
+                    panic!("the keys of a `SymbolMap` or `SymbolSet` must be unique");
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+        let mut seed = 0;
+        loop {
+            if let Some(hash) = Self::try_seed(keys, seed) {
+                return hash;
+            }
+            seed += 1;
+        }
+    }
+
+    /// Attempts to find displacements for every bucket using the specified seed, placing
+    /// the largest buckets first.
+    const fn try_seed(keys: &[[u64; 4]; LEN], seed: u64) -> Option<Self> {
+        let mut hash = __PerfectHash {
+            seed,
+            displacements: [(0, 0); BUCKETS],
+            slots: [0; LEN],
+        };
+        if LEN == 0 {
+            return Some(hash);
+        }
+        let mut hashes = [(0, 0, 0); LEN];
+        // the number of keys in each bucket, and then (after the prefix sum) the index into
+        // `members` at which each bucket ends
+        let mut bucket_ends = [0; BUCKETS];
+        let mut i = 0;
+        while i < LEN {
+            hashes[i] = perfect_hash(keys[i], seed);
+            bucket_ends[hashes[i].0 as usize % BUCKETS] += 1;
+            i += 1;
+        }
+        let mut bucket_sizes = [0; BUCKETS];
+        let mut total = 0;
+        let mut b = 0;
+        while b < BUCKETS {
+            bucket_sizes[b] = bucket_ends[b];
+            total += bucket_ends[b];
+            bucket_ends[b] = total;
+            b += 1;
+        }
+        // the indices of the keys in each bucket, grouped by bucket
+        let mut members = [0; LEN];
+        let mut fill = bucket_ends;
+        let mut i = LEN;
+        while i > 0 {
+            i -= 1;
+            let bucket = hashes[i].0 as usize % BUCKETS;
+            fill[bucket] -= 1;
+            members[fill[bucket]] = i;
+        }
+        let mut occupied = [false; LEN];
+        // used to detect keys within the same bucket colliding with each other, by recording
+        // the attempt in which each slot was last claimed
+        let mut claimed = [0; LEN];
+        let mut attempt = 0;
+        let mut placed = [false; BUCKETS];
+        let mut remaining = BUCKETS;
+        while remaining > 0 {
+            let mut bucket = 0;
+            let mut b = 0;
+            while b < BUCKETS {
+                if !placed[b] && (placed[bucket] || bucket_sizes[b] > bucket_sizes[bucket]) {
+                    bucket = b;
+                }
+                b += 1;
+            }
+            placed[bucket] = true;
+            remaining -= 1;
+            let start = bucket_ends[bucket] - bucket_sizes[bucket];
+            let end = bucket_ends[bucket];
+            if start == end {
+                continue;
+            }
+            let mut displacement = None;
+            let mut d1 = 0;
+            'search: while d1 < LEN as u32 {
+                let mut d2 = 0;
+                while d2 < LEN as u32 {
+                    attempt += 1;
+                    let mut fits = true;
+                    let mut m = start;
+                    while m < end {
+                        let (_, f1, f2) = hashes[members[m]];
+                        let slot = displace(f1, f2, d1, d2) as usize % LEN;
+                        if occupied[slot] || claimed[slot] == attempt {
+                            fits = false;
+                            break;
+                        }
+                        claimed[slot] = attempt;
+                        m += 1;
+                    }
+                    if fits {
+                        displacement = Some((d1, d2));
+                        break 'search;
+                    }
+                    d2 += 1;
+                }
+                d1 += 1;
+            }
+            let Some((d1, d2)) = displacement else {
+                return None;
+            };
+            hash.displacements[bucket] = (d1, d2);
+            let mut m = start;
+            while m < end {
+                let (_, f1, f2) = hashes[members[m]];
+                let slot = displace(f1, f2, d1, d2) as usize % LEN;
+                occupied[slot] = true;
+                hash.slots[slot] = members[m];
+                m += 1;
+            }
+        }
+        Some(hash)
+    }
+}
+
+/// Compares two sets of words for equality in `const` contexts.
+const fn words_eq(a: [u64; 4], b: [u64; 4]) -> bool {
+    a[0] == b[0] && a[1] == b[1] && a[2] == b[2] && a[3] == b[3]
+}
+
+/// The SplitMix64 finalizer, a cheap way to thoroughly mix the bits of a `u64`.
+const fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Hashes the words of a symbol using the specified seed, producing the bucket hash `g` and
+/// the two hashes `f1` and `f2` used to find its slot within that bucket's displacement.
+const fn perfect_hash(words: [u64; 4], seed: u64) -> (u32, u32, u32) {
+    let mut h = mix(seed ^ 0x243f_6a88_85a3_08d3);
+    let mut i = 0;
+    while i < 4 {
+        h = mix(h ^ words[i]);
+        i += 1;
+    }
+    let h2 = mix(h ^ 0x9e37_79b9_7f4a_7c15);
+    ((h >> 32) as u32, h as u32, h2 as u32)
+}
+
+/// Determines the slot (before reduction by the table length) of a key from its hashes and
+/// the displacement of its bucket.
+const fn displace(f1: u32, f2: u32, d1: u32, d2: u32) -> u32 {
+    d2.wrapping_add(f1.wrapping_mul(d1)).wrapping_add(f2)
+}
@@ -0,0 +1,271 @@
+//! Contains [`SymbolRepr`], the trait implemented by the integer types that can be used as the
+//! backing storage of a [`CustomSymbol`](crate::CustomSymbol), as well as [`U256`].
+
+use core::{fmt::Debug, hash::Hash, marker::PhantomData};
+
+use crate::{Alphabet, NameBuffer};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// An unsigned integer type that can be used as the backing storage of a
+/// [`CustomSymbol`](crate::CustomSymbol). This is implemented for [`u32`], [`u64`], [`u128`]
+/// and [`U256`], and cannot be implemented outside of this crate.
+///
+/// Wider types allow for longer symbols (see `CustomSymbol::MAX_SYMBOL_LEN`), while narrower
+/// types take up less memory. Each character is stored as a base-`(N + 1)` digit, so for the
+/// default alphabet of 27 characters this works out to 6, 13, 26 and 53 characters
+/// respectively.
+pub trait SymbolRepr:
+    Copy + Clone + PartialEq + Eq + PartialOrd + Ord + Hash + Debug + Default + sealed::Sealed
+{
+    /// The number of bits in this [`SymbolRepr`].
+    const BITS: u32;
+
+    /// The zero value of this [`SymbolRepr`], which encodes an empty symbol.
+    const ZERO: Self;
+
+    /// The [`Alphabet`]'s buffer large enough to hold the name of any symbol backed by this
+    /// [`SymbolRepr`], used as the backing for [`SymbolName`](crate::SymbolName).
+    #[doc(hidden)]
+    type NameBuffer<const N: usize, A: Alphabet<N>>: NameBuffer;
+
+    /// Returns `self * base + digit`, or [`None`] if the result would overflow.
+    #[doc(hidden)]
+    fn push_digit(self, base: u128, digit: u128) -> Option<Self>;
+
+    /// Divides `self` by `base` in place, returning the remainder.
+    #[doc(hidden)]
+    fn pop_digit(&mut self, base: u128) -> u128;
+
+    /// Returns `true` if `self` is zero.
+    #[doc(hidden)]
+    fn is_zero(&self) -> bool;
+
+    /// Converts the specified [`u128`] into this [`SymbolRepr`], or [`None`] if it does not fit.
+    #[doc(hidden)]
+    fn from_u128(value: u128) -> Option<Self>;
+
+    /// Returns the little-endian 64-bit words of `self`, zero-extended to 256 bits.
+    #[doc(hidden)]
+    fn words(&self) -> [u64; 4];
+}
+
+/// Provides `const` versions of the [`SymbolRepr`] operations for each concrete
+/// [`SymbolRepr`], since trait methods cannot be called in `const` contexts.
+pub(crate) struct ReprOps<R>(PhantomData<R>);
+
+macro_rules! impl_symbol_repr {
+    ($($repr:ident => $name_buffer:ident),*) => {
+        $(
+            impl sealed::Sealed for $repr {}
+
+            impl ReprOps<$repr> {
+                pub(crate) const fn push_digit(data: $repr, base: u128, digit: u128) -> Option<$repr> {
+                    match data.checked_mul(base as $repr) {
+                        Some(data) => data.checked_add(digit as $repr),
+                        None => None,
+                    }
+                }
+
+                pub(crate) const fn pop_digit(data: &mut $repr, base: u128) -> u128 {
+                    let digit = *data % base as $repr;
+                    *data /= base as $repr;
+                    digit as u128
+                }
+
+                pub(crate) const fn is_zero(data: $repr) -> bool {
+                    data == 0
+                }
+
+                pub(crate) const fn words(data: $repr) -> [u64; 4] {
+                    let data = data as u128;
+                    [data as u64, (data >> 64) as u64, 0, 0]
+                }
+            }
+
+            impl SymbolRepr for $repr {
+                const BITS: u32 = $repr::BITS;
+                const ZERO: Self = 0;
+
+                type NameBuffer<const N: usize, A: Alphabet<N>> = A::$name_buffer;
+
+                fn push_digit(self, base: u128, digit: u128) -> Option<Self> {
+                    ReprOps::<$repr>::push_digit(self, base, digit)
+                }
+
+                fn pop_digit(&mut self, base: u128) -> u128 {
+                    ReprOps::<$repr>::pop_digit(self, base)
+                }
+
+                fn is_zero(&self) -> bool {
+                    ReprOps::<$repr>::is_zero(*self)
+                }
+
+                fn from_u128(value: u128) -> Option<Self> {
+                    $repr::try_from(value).ok()
+                }
+
+                fn words(&self) -> [u64; 4] {
+                    ReprOps::<$repr>::words(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_symbol_repr!(u32 => NameBuffer32, u64 => NameBuffer64, u128 => NameBuffer128);
+
+/// A minimal 256-bit unsigned integer, stored as two [`u128`] words, for use as a
+/// [`SymbolRepr`] when symbols longer than a [`u128`] allows are needed.
+///
+/// Only the operations needed to encode and decode symbols are provided, so this is not
+/// intended as a general purpose big integer type.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct U256 {
+    // `hi` must come first so the derived `Ord` compares numerically
+    hi: u128,
+    lo: u128,
+}
+
+impl U256 {
+    /// The value `0`.
+    pub const ZERO: U256 = U256::new(0, 0);
+
+    /// The largest value that can be represented by a [`U256`], i.e. `2^256 - 1`.
+    pub const MAX: U256 = U256::new(u128::MAX, u128::MAX);
+
+    /// Creates a [`U256`] from its high and low 128-bit words, i.e. `hi * 2^128 + lo`.
+    pub const fn new(hi: u128, lo: u128) -> Self {
+        U256 { hi, lo }
+    }
+
+    /// Returns the high 128-bit word of this [`U256`].
+    pub const fn hi(self) -> u128 {
+        self.hi
+    }
+
+    /// Returns the low 128-bit word of this [`U256`].
+    pub const fn lo(self) -> u128 {
+        self.lo
+    }
+
+    /// Returns the number of bits needed to represent this [`U256`], i.e. `256` minus the
+    /// number of leading zeros.
+    pub(crate) const fn bit_len(self) -> u32 {
+        if self.hi != 0 {
+            256 - self.hi.leading_zeros()
+        } else {
+            128 - self.lo.leading_zeros()
+        }
+    }
+
+    /// Splits this [`U256`] into four little-endian 64-bit limbs.
+    pub(crate) const fn limbs(self) -> [u64; 4] {
+        [
+            self.lo as u64,
+            (self.lo >> 64) as u64,
+            self.hi as u64,
+            (self.hi >> 64) as u64,
+        ]
+    }
+
+    /// The inverse of [`U256::limbs`].
+    const fn from_limbs(limbs: [u64; 4]) -> Self {
+        U256::new(
+            (limbs[3] as u128) << 64 | limbs[2] as u128,
+            (limbs[1] as u128) << 64 | limbs[0] as u128,
+        )
+    }
+
+    /// Returns `self * mul + add`, or [`None`] if the result would overflow.
+    pub(crate) const fn checked_mul_add(self, mul: u64, add: u64) -> Option<Self> {
+        let limbs = self.limbs();
+        let mut result = [0; 4];
+        let mut carry = add as u128;
+        let mut i = 0;
+        while i < 4 {
+            let value = limbs[i] as u128 * mul as u128 + carry;
+            result[i] = value as u64;
+            carry = value >> 64;
+            i += 1;
+        }
+        if carry != 0 {
+            return None;
+        }
+        Some(U256::from_limbs(result))
+    }
+
+    /// Returns `(self / div, self % div)`.
+    pub(crate) const fn div_rem(self, div: u64) -> (Self, u64) {
+        let limbs = self.limbs();
+        let mut result = [0; 4];
+        let mut rem: u128 = 0;
+        let mut i = 4;
+        while i > 0 {
+            i -= 1;
+            let value = rem << 64 | limbs[i] as u128;
+            result[i] = (value / div as u128) as u64;
+            rem = value % div as u128;
+        }
+        (U256::from_limbs(result), rem as u64)
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(value: u128) -> Self {
+        U256::new(0, value)
+    }
+}
+
+impl sealed::Sealed for U256 {}
+
+// bases and digits are always at most `N + 1` for an alphabet of length `N`, so they always
+// fit in a `u64`
+impl ReprOps<U256> {
+    pub(crate) const fn push_digit(data: U256, base: u128, digit: u128) -> Option<U256> {
+        data.checked_mul_add(base as u64, digit as u64)
+    }
+
+    pub(crate) const fn pop_digit(data: &mut U256, base: u128) -> u128 {
+        let (quotient, digit) = data.div_rem(base as u64);
+        *data = quotient;
+        digit as u128
+    }
+
+    pub(crate) const fn is_zero(data: U256) -> bool {
+        data.hi == 0 && data.lo == 0
+    }
+
+    pub(crate) const fn words(data: U256) -> [u64; 4] {
+        data.limbs()
+    }
+}
+
+impl SymbolRepr for U256 {
+    const BITS: u32 = 256;
+    const ZERO: Self = U256::ZERO;
+
+    type NameBuffer<const N: usize, A: Alphabet<N>> = A::NameBuffer256;
+
+    fn push_digit(self, base: u128, digit: u128) -> Option<Self> {
+        ReprOps::<U256>::push_digit(self, base, digit)
+    }
+
+    fn pop_digit(&mut self, base: u128) -> u128 {
+        ReprOps::<U256>::pop_digit(self, base)
+    }
+
+    fn is_zero(&self) -> bool {
+        ReprOps::<U256>::is_zero(*self)
+    }
+
+    fn from_u128(value: u128) -> Option<Self> {
+        Some(U256::from(value))
+    }
+
+    fn words(&self) -> [u64; 4] {
+        ReprOps::<U256>::words(*self)
+    }
+}
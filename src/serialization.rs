@@ -0,0 +1,149 @@
+//! Optional [`serde`](https://serde.rs) support for [`CustomSymbol`] / [`Symbol`](crate::Symbol),
+//! enabled via the `serde` cargo feature.
+//!
+//! By default, [`CustomSymbol`] serializes as its human-readable name (e.g. `"hello_world"`)
+//! when the underlying format is human-readable (JSON, TOML, YAML, etc.), and as its raw
+//! backing [`SymbolRepr`] for binary formats (bincode, postcard, etc.). The [`name`] and
+//! [`raw`] modules can be used with `#[serde(with = "...")]` to force one representation
+//! regardless of the format. [`U256`] is (de)serialized as a `(hi, lo)` tuple of [`u128`]s.
+//!
+//! Deserialization always validates its input: names are parsed using the usual
+//! [`TryFrom<&str>`] rules (and thus [`Alphabet::invert_char`]), and raw values are rejected
+//! if they do not decode to a valid symbol in the target [`Alphabet`].
+
+use core::{fmt::Formatter, marker::PhantomData};
+
+use ::serde::{
+    de::{Error, Unexpected, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{Alphabet, CustomSymbol, SymbolRepr, U256};
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr + Serialize> Serialize
+    for CustomSymbol<N, A, R>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            name::serialize(self, serializer)
+        } else {
+            raw::serialize(self, serializer)
+        }
+    }
+}
+
+impl<'de, const N: usize, A: Alphabet<N>, R: SymbolRepr + Deserialize<'de>> Deserialize<'de>
+    for CustomSymbol<N, A, R>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            name::deserialize(deserializer)
+        } else {
+            raw::deserialize(deserializer)
+        }
+    }
+}
+
+/// Accepts either a name or a raw integer, validating both against the [`Alphabet`].
+struct SymbolVisitor<const N: usize, A: Alphabet<N>, R: SymbolRepr>(PhantomData<(A, R)>);
+
+impl<const N: usize, A: Alphabet<N>, R: SymbolRepr> SymbolVisitor<N, A, R> {
+    fn validate_raw<E: Error>(&self, v: R) -> Result<CustomSymbol<N, A, R>, E> {
+        if !crate::raw_is_valid::<N, A, R>(v) {
+            return Err(E::invalid_value(
+                Unexpected::Other("raw value that is not a valid symbol"),
+                self,
+            ));
+        }
+        Ok(CustomSymbol::from_raw_unchecked(v))
+    }
+}
+
+impl<'de, const N: usize, A: Alphabet<N>, R: SymbolRepr> Visitor<'de> for SymbolVisitor<N, A, R> {
+    type Value = CustomSymbol<N, A, R>;
+
+    fn expecting(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "a symbol of at most {} characters from its alphabet",
+            CustomSymbol::<N, A, R>::MAX_SYMBOL_LEN
+        )
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        CustomSymbol::try_from(v).map_err(E::custom)
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        self.visit_u128(v as u128)
+    }
+
+    fn visit_u128<E: Error>(self, v: u128) -> Result<Self::Value, E> {
+        match R::from_u128(v) {
+            Some(v) => self.validate_raw(v),
+            None => Err(E::invalid_value(
+                Unexpected::Other("out of range integer"),
+                &self,
+            )),
+        }
+    }
+}
+
+/// Forces a [`CustomSymbol`] to be (de)serialized as its human-readable name, regardless of
+/// whether the format is human-readable. For use with `#[serde(with = "smol_symbol::serialization::name")]`.
+pub mod name {
+    use super::*;
+
+    pub fn serialize<const N: usize, A: Alphabet<N>, R: SymbolRepr, S: Serializer>(
+        symbol: &CustomSymbol<N, A, R>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&symbol.inline_name())
+    }
+
+    pub fn deserialize<'de, const N: usize, A: Alphabet<N>, R: SymbolRepr, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CustomSymbol<N, A, R>, D::Error> {
+        deserializer.deserialize_str(SymbolVisitor(PhantomData))
+    }
+}
+
+/// Forces a [`CustomSymbol`] to be (de)serialized as its raw backing [`SymbolRepr`],
+/// regardless of whether the format is human-readable. For use with
+/// `#[serde(with = "smol_symbol::serialization::raw")]`.
+pub mod raw {
+    use super::*;
+
+    pub fn serialize<const N: usize, A: Alphabet<N>, R: SymbolRepr + Serialize, S: Serializer>(
+        symbol: &CustomSymbol<N, A, R>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        symbol.data.serialize(serializer)
+    }
+
+    pub fn deserialize<
+        'de,
+        const N: usize,
+        A: Alphabet<N>,
+        R: SymbolRepr + Deserialize<'de>,
+        D: Deserializer<'de>,
+    >(
+        deserializer: D,
+    ) -> Result<CustomSymbol<N, A, R>, D::Error> {
+        let raw = R::deserialize(deserializer)?;
+        SymbolVisitor(PhantomData).validate_raw(raw)
+    }
+}
+
+impl Serialize for U256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.hi(), self.lo()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for U256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (hi, lo) = <(u128, u128)>::deserialize(deserializer)?;
+        Ok(U256::new(hi, lo))
+    }
+}
@@ -25,9 +25,9 @@ fn symbol_example() {
     let dynamic_sym = Symbol::try_from(some_string).unwrap();
     assert_eq!(dynamic_sym, s!(some_random_string));
 
-    // Can't be longer than 25 characters
-    assert!(Symbol::try_from("this_is_too_long_to_store_").is_err());
-    assert!(Symbol::try_from("this_is_just_short_enough").is_ok());
+    // Can't be longer than 26 characters
+    assert!(Symbol::try_from("this_is_too_long_to_store__").is_err());
+    assert!(Symbol::try_from("this_is_just_short_enough_").is_ok());
 
     // Character alphabet is limited to lowercase a-z and _
     assert!(Symbol::try_from("this-is-invalid").is_err());
@@ -88,6 +88,26 @@ fn test_roundtrip() {
     );
 }
 
+#[test]
+fn test_max_symbol_len() {
+    // 28^26 <= 2^128 < 28^27
+    assert_eq!(DefaultAlphabet::MAX_SYMBOL_LEN, 26);
+    const LONGEST: Symbol = s!(abcdefghijklmnopqrstuvwxyz);
+    assert_eq!(LONGEST.to_string(), "abcdefghijklmnopqrstuvwxyz");
+    assert_eq!(
+        Symbol::try_from("__________________________").unwrap(),
+        s!(__________________________)
+    );
+
+    // 16^32 == 2^128, so every u128 is a valid symbol in a 15-character alphabet
+    custom_alphabet!(Fifteen, abcdefghijklmno);
+    assert_eq!(Fifteen::MAX_SYMBOL_LEN, 32);
+    let sym =
+        CustomSymbol::<{ Fifteen::LEN }, Fifteen>::try_from("oooooooooooooooooooooooooooooooo");
+    assert_eq!(u128::from(sym.unwrap()), u128::MAX);
+    assert_eq!(s!(oooooooooooooooooooooooooooooooo, Fifteen), sym.unwrap());
+}
+
 #[test]
 fn test_debug() {
     assert_eq!(
@@ -96,18 +116,130 @@ fn test_debug() {
     );
 }
 
+#[docify::export]
+#[test]
+fn lex_symbol_example() {
+    // Symbols are ordered by their backing u128, which doesn't match lexicographic order
+    assert!(s!(b) < s!(ab));
+
+    // LexSymbols are ordered the same way their names are
+    let lex = |sym: Symbol| LexSymbol::from(sym);
+    assert!(lex(s!(b)) > lex(s!(ab)));
+    assert!(lex(s!(a)) < lex(s!(ab)));
+    assert!(lex(s!(_a)) < lex(s!(a_)));
+    assert!(s!(b).lex_cmp(&s!(ab)).is_gt());
+
+    // They can be built in const contexts and converted back into symbols
+    const LEX: LexSymbol = LexSymbol::from_symbol(s!(hello_world));
+    assert_eq!(LEX.to_symbol(), s!(hello_world));
+    assert_eq!(LEX.to_string(), "hello_world");
+    assert_eq!(LexSymbol::try_from("hello_world").unwrap(), LEX);
+}
+
+#[docify::export]
+#[test]
+fn symbol_prefix_example() {
+    use std::collections::BTreeMap;
+
+    let mut routes = BTreeMap::new();
+    for name in ["http_get", "http_post", "https_get", "grpc_call", "http"] {
+        routes.insert(LexSymbol::try_from(name).unwrap(), name.len());
+    }
+
+    // all keys starting with `http_`, found via `BTreeMap::range` without decoding any keys
+    let http = routes.starting_with(s!(http_)).map(|(k, _)| k.to_string());
+    assert_eq!(http.collect::<Vec<_>>(), ["http_get", "http_post"]);
+
+    // the prefix itself is included, as are keys that only extend it
+    let range = LexSymbol::from(s!(http)).prefix_range();
+    assert_eq!(routes.range(range).count(), 4);
+    assert_eq!(routes.starting_with(s!(ftp)).count(), 0);
+}
+
+#[docify::export]
+#[test]
+fn dyn_symbol_example() {
+    use std::collections::HashSet;
+
+    // symbols from different alphabets can be stored side by side
+    let symbols: Vec<DynSymbol> = vec![
+        s!(hello_world).into(),
+        s!("deadbeef", Hex).into(),
+        s!(MAX_VALUE, UpperSnake).into(),
+    ];
+    let names: Vec<String> = symbols.iter().map(|sym| sym.to_string()).collect();
+    assert_eq!(names, ["hello_world", "deadbeef", "MAX_VALUE"]);
+
+    // the same name in a different alphabet is a different symbol
+    let keys: HashSet<DynSymbol> = [s!(cafe).into(), s!(cafe, Hex).into()].into();
+    assert_eq!(keys.len(), 2);
+
+    // and they can be downcast back into regular symbols
+    assert_eq!(symbols[1].downcast::<16, Hex>(), Some(s!("deadbeef", Hex)));
+    assert_eq!(symbols[1].downcast::<27, DefaultAlphabet>(), None);
+}
+
+#[docify::export]
+#[test]
+fn symbol_map_example() {
+    // built at compile time, using a perfect hash function found during const-eval
+    static OPCODES: SymbolMap<u8> = symbol_map! {
+        push => 0x01,
+        pop => 0x02,
+        "jump" => 0x03,
+        r#return => 0x04,
+    };
+    assert_eq!(OPCODES.get(&s!(pop)), Some(&0x02));
+    assert_eq!(OPCODES.get(&s!(peek)), None);
+    assert_eq!(OPCODES.len(), 4);
+
+    // entries are iterated in declaration order
+    let names: Vec<String> = OPCODES.keys().map(|key| key.to_string()).collect();
+    assert_eq!(names, ["push", "pop", "jump", "return"]);
+
+    // sets work the same way
+    static KEYWORDS: SymbolSet = symbol_set! { r#if, r#else, r#while };
+    assert!(KEYWORDS.contains(&s!(while)));
+    assert!(!KEYWORDS.contains(&s!(loop)));
+}
+
+#[cfg(feature = "std")]
+#[docify::export]
+#[test]
+fn interned_symbol_example() {
+    // names that are valid symbols are stored inline, exactly like a `Symbol`
+    let short = InternedSymbol::new("hello_world");
+    assert!(short.is_inline());
+    assert_eq!(short.to_symbol(), Some(s!(hello_world)));
+
+    // anything else falls back to the global interner
+    let long = InternedSymbol::new("this_name_is_far_too_long_to_fit_inline");
+    let upper = InternedSymbol::new("Hello-World");
+    assert!(!long.is_inline());
+    assert_eq!(upper.name(), "Hello-World");
+
+    // either way, symbols are `Copy` and compare / hash without looking at their names
+    assert_eq!(
+        long,
+        InternedSymbol::from("this_name_is_far_too_long_to_fit_inline")
+    );
+    assert_ne!(long, upper);
+    assert_eq!(short, InternedSymbol::from(s!(hello_world)));
+}
+
 use smol_symbol::*;
 
 custom_alphabet!(
     Ferris,
-    ABCDEFGHIJKLMNOPQRSTVWXYZ東京_abcdefghijklmnopqrstuvwxyz12345678910
+    ABCDEFGHIJKLMNOPQRSTVWXYZ東京_abcdefghijklmnopqrstuvwxyz1234567890
 );
-custom_alphabet!(Short, hello_world);
+custom_alphabet!(Short, hello_world, dedup);
 
 #[test]
 fn test_custom_alphabets() {
-    assert_eq!(Ferris::MAX_SYMBOL_LEN, 18);
-    assert_eq!(Short::MAX_SYMBOL_LEN, 32);
+    assert_eq!(Ferris::MAX_SYMBOL_LEN, 21);
+    assert_eq!(Short::LEN, 8);
+    assert_eq!(Short::MAX_SYMBOL_LEN, 40);
     let sym1 = s!(hello東_world京_33, Ferris);
     let sym2 = s!(hello_world, Ferris);
     let sym3 = s!(hello_world, Short);
@@ -123,3 +255,1289 @@ fn test_custom_alphabets() {
     let sym5 = s!(HELLO_WORLD, Ferris);
     assert_ne!(sym2, sym5);
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use ::serde::{Deserialize, Serialize};
+
+    // human-readable formats use the name, binary formats use the raw u128
+    let sym = s!(hello_world);
+    assert_eq!(serde_json::to_string(&sym).unwrap(), "\"hello_world\"");
+    assert_eq!(
+        serde_json::from_str::<Symbol>("\"hello_world\"").unwrap(),
+        sym
+    );
+    let bytes = bincode::serialize(&sym).unwrap();
+    assert_eq!(bytes, u128::from(sym).to_le_bytes());
+    assert_eq!(bincode::deserialize::<Symbol>(&bytes).unwrap(), sym);
+
+    // invalid input is rejected
+    assert!(serde_json::from_str::<Symbol>("\"Hello\"").is_err());
+    assert!(serde_json::from_str::<Symbol>("\"\"").is_err());
+    assert!(serde_json::from_str::<Symbol>("\"this_is_too_long_to_store__\"").is_err());
+    assert!(bincode::deserialize::<Symbol>(&0u128.to_le_bytes()).is_err());
+    assert!(bincode::deserialize::<Symbol>(&28u128.to_le_bytes()).is_err());
+    assert!(bincode::deserialize::<Symbol>(&u128::MAX.to_le_bytes()).is_err());
+
+    // symbols produced by slicing are never empty, so they always round-trip
+    let parts = [
+        sym.strip_prefix(s!(hello_)),
+        sym.strip_suffix(s!(_world)),
+        sym.slice(10..11),
+        sym.split_at(1).map(|(left, _)| left),
+        sym.split_at(10).map(|(_, right)| right),
+    ];
+    for part in parts.map(Option::unwrap) {
+        let json = serde_json::to_string(&part).unwrap();
+        assert_eq!(serde_json::from_str::<Symbol>(&json).unwrap(), part);
+        let bytes = bincode::serialize(&part).unwrap();
+        assert_eq!(bincode::deserialize::<Symbol>(&bytes).unwrap(), part);
+    }
+    assert_eq!(sym.strip_prefix(sym), None);
+    assert_eq!(sym.slice(0..0), None);
+
+    // custom alphabets are validated against their own characters
+    let custom = s!(Hello_東京, Ferris);
+    let json = serde_json::to_string(&custom).unwrap();
+    assert_eq!(json, "\"Hello_東京\"");
+    assert_eq!(
+        serde_json::from_str::<CustomSymbol<{ Ferris::LEN }, Ferris>>(&json).unwrap(),
+        custom
+    );
+    assert!(serde_json::from_str::<CustomSymbol<{ Short::LEN }, Short>>("\"hello-\"").is_err());
+
+    // the representation can be forced either way
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Forced {
+        #[serde(with = "smol_symbol::serialization::raw")]
+        raw: Symbol,
+        #[serde(with = "smol_symbol::serialization::name")]
+        name: Symbol,
+    }
+    let forced = Forced {
+        raw: s!(raw),
+        name: s!(name),
+    };
+    let json = serde_json::to_string(&forced).unwrap();
+    assert_eq!(
+        json,
+        format!("{{\"raw\":{},\"name\":\"name\"}}", u128::from(s!(raw)))
+    );
+    assert_eq!(serde_json::from_str::<Forced>(&json).unwrap(), forced);
+    let bytes = bincode::serialize(&forced).unwrap();
+    assert_eq!(bincode::deserialize::<Forced>(&bytes).unwrap(), forced);
+
+    // other reprs use their own raw encoding
+    let compact = s!(compact, Compact);
+    let bytes = bincode::serialize(&compact).unwrap();
+    assert_eq!(bytes, u64::from(compact).to_le_bytes());
+    assert_eq!(
+        bincode::deserialize::<CustomSymbol<27, Compact>>(&bytes).unwrap(),
+        compact
+    );
+    let wide = s!(wide, DefaultAlphabet, U256);
+    let bytes = bincode::serialize(&wide).unwrap();
+    assert_eq!(bytes.len(), 32);
+    assert_eq!(
+        bincode::deserialize::<CustomSymbol<27, DefaultAlphabet, U256>>(&bytes).unwrap(),
+        wide
+    );
+    assert!(bincode::deserialize::<CustomSymbol<27, DefaultAlphabet, U256>>(&[0xff; 32]).is_err());
+}
+
+#[test]
+fn test_parsing_errors() {
+    assert_eq!(Symbol::try_from(""), Err(SymbolParsingError::Empty));
+    assert_eq!(
+        Symbol::try_from("this_is_too_long_to_store__"),
+        Err(SymbolParsingError::TooLong { len: 27, max: 26 })
+    );
+    assert_eq!(
+        Symbol::try_from("hello-world"),
+        Err(SymbolParsingError::InvalidChar { ch: '-', index: 5 })
+    );
+    assert_eq!(
+        Symbol::try_from("Hello_world"),
+        Err(SymbolParsingError::InvalidChar { ch: 'H', index: 0 })
+    );
+    assert_eq!(
+        CustomSymbol::<{ Ferris::LEN }, Ferris>::try_from("東京-"),
+        Err(SymbolParsingError::InvalidChar { ch: '-', index: 2 })
+    );
+    assert_eq!(
+        DefaultAlphabet::invert_char('?'),
+        Err(SymbolParsingError::InvalidChar { ch: '?', index: 0 })
+    );
+    assert_eq!(
+        DefaultAlphabet::parse_chars(&['o', 'k', '!']),
+        Err(SymbolParsingError::InvalidChar { ch: '!', index: 2 })
+    );
+    assert_eq!(
+        DefaultAlphabet::parse_chars(&[]),
+        Err(SymbolParsingError::Empty)
+    );
+    assert_eq!(
+        DefaultAlphabet::parse_chars(&['a'; 27]),
+        Err(SymbolParsingError::TooLong { len: 27, max: 26 })
+    );
+    assert_eq!(
+        Short::parse_chars(&['h'; 41]),
+        Err(SymbolParsingError::TooLong { len: 41, max: 40 })
+    );
+    assert_eq!(
+        Short::parse_chars(&['h'; 40]).map(u128::from),
+        CustomSymbol::<{ Short::LEN }, Short>::try_from("h".repeat(40).as_str()).map(u128::from)
+    );
+    assert_eq!(
+        SymbolParsingError::InvalidChar { ch: '-', index: 5 }.to_string(),
+        "invalid character '-' at position 5, which is not in the `Alphabet`"
+    );
+    assert_eq!(
+        SymbolParsingError::TooLong { len: 26, max: 25 }.to_string(),
+        "symbol is 26 characters long, but its `Alphabet` allows at most 25"
+    );
+    assert_eq!(
+        SymbolParsingError::Empty.message(),
+        "a `Symbol` or `CustomSymbol` cannot be empty"
+    );
+    let err: Box<dyn std::error::Error> = Box::new(SymbolParsingError::Overflow);
+    assert_eq!(err.to_string(), SymbolParsingError::Overflow.message());
+}
+
+#[test]
+fn test_compile_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
+
+#[test]
+fn test_lex_ordering() {
+    let mut names = vec![
+        "b",
+        "ab",
+        "a",
+        "a_",
+        "_",
+        "_a",
+        "zzz",
+        "z",
+        "hello",
+        "hello_world",
+        "hell",
+        "help",
+        "abcdefghijklmnopqrstuvwxy",
+        "_________________________",
+        "zzzzzzzzzzzzzzzzzzzzzzzzz",
+    ];
+    let mut sorted = names
+        .iter()
+        .map(|name| LexSymbol::try_from(*name).unwrap())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .map(|sym| sym.name())
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(sorted, names);
+
+    // lex_cmp agrees with the LexSymbol ordering
+    sorted.reverse();
+    let mut syms = sorted
+        .iter()
+        .map(|name| Symbol::try_from(name).unwrap())
+        .collect::<Vec<_>>();
+    syms.sort_by(Symbol::lex_cmp);
+    assert_eq!(syms.iter().map(Symbol::name).collect::<Vec<_>>(), names);
+
+    // custom alphabets with non-ASCII characters are ordered by char
+    type FerrisLex = CustomLexSymbol<{ Ferris::LEN }, Ferris>;
+    let a = FerrisLex::from_symbol(s!(東, Ferris));
+    let b = FerrisLex::from_symbol(s!(京, Ferris));
+    let c = FerrisLex::from_symbol(s!(z9, Ferris));
+    assert!(b < a && c < b);
+    assert_eq!(a.to_symbol(), s!(東, Ferris));
+    assert_eq!(format!("{}", c), "z9");
+
+    // raw values round-trip between the two encodings
+    for name in names {
+        let sym = Symbol::try_from(name).unwrap();
+        let lex = LexSymbol::from_raw_unchecked(u128::from(LexSymbol::from(sym)));
+        assert_eq!(Symbol::from(lex), sym);
+    }
+}
+
+#[test]
+fn test_inline_name() {
+    let name = s!(hello_world).inline_name();
+    assert_eq!(name, "hello_world");
+    assert_eq!(name.len(), 11);
+    assert_eq!(name.as_str(), s!(hello_world).name());
+    assert_eq!(format!("{name:?}"), "\"hello_world\"");
+    assert_eq!(
+        s!(_________________________).inline_name(),
+        "_________________________"
+    );
+    let custom = s!(hello東_world京_33, Ferris).inline_name();
+    assert_eq!(custom, "hello東_world京_33");
+    assert_eq!(custom.chars().count(), 16);
+
+    // Display writes directly to the formatter
+    let mut buf = [0u8; 32];
+    let mut cursor = std::io::Cursor::new(&mut buf[..]);
+    use std::io::Write;
+    write!(cursor, "{}|{}", s!(foo), s!(東京, Ferris)).unwrap();
+    let len = cursor.position() as usize;
+    assert_eq!(std::str::from_utf8(&buf[..len]).unwrap(), "foo|東京");
+}
+
+custom_alphabet!(Kanji, 東京大阪);
+
+/// Asserts that the const-eval parsing paths (used by `s!` and `try_from_str`) and the runtime
+/// [`TryFrom<&str>`] impl agree on the result (including errors) for every provided string,
+/// and that `s!` produces the same symbol for every provided ident. Alphabets shipped with
+/// `smol-symbol` are referred to as `smol_symbol::Name`, so that `s!` validates the idents
+/// itself rather than leaving this to const-eval.
+macro_rules! cross_check {
+    (smol_symbol::$alphabet:ident, $inputs:tt, [$($ident:ident),* $(,)?]) => {{
+        cross_check!($alphabet, $inputs, []);
+        $(
+            assert_eq!(
+                s!($ident, smol_symbol::$alphabet),
+                CustomSymbol::<{ $alphabet::LEN }, $alphabet>::try_from(stringify!($ident))
+                    .unwrap()
+            );
+        )*
+    }};
+    ($alphabet:ident, [$($input:expr),* $(,)?], [$($ident:ident),* $(,)?]) => {{
+        type Sym = CustomSymbol<{ $alphabet::LEN }, $alphabet>;
+        let alphabet: String = $alphabet::ALPHABET.iter().collect();
+        let max = $alphabet::MAX_SYMBOL_LEN;
+        let first = $alphabet::ALPHABET[0].to_string();
+        let last = $alphabet::ALPHABET[$alphabet::LEN - 1].to_string();
+        let inputs: Vec<String> = vec![
+            String::new(),
+            alphabet.chars().take(max).collect(),
+            first.repeat(max),
+            last.repeat(max),
+            last.repeat(max + 1),
+            format!("{first}-{last}"),
+            format!("{first} "),
+            $(String::from($input)),*
+        ];
+        for input in inputs {
+            let chars: Vec<char> = input.chars().collect();
+            assert_eq!(
+                $alphabet::parse_chars(&chars),
+                Sym::try_from(input.as_str()),
+                "{} disagrees on {:?}",
+                stringify!($alphabet),
+                input
+            );
+            assert_eq!(
+                Sym::try_from_str(&input),
+                Sym::try_from(input.as_str()),
+                "{} disagrees on {:?}",
+                stringify!($alphabet),
+                input
+            );
+            assert_eq!($alphabet::parse_str(&input), input.parse::<Sym>());
+            if let Ok(sym) = Sym::try_from(input.as_str()) {
+                // normalized input decodes to the canonical characters instead
+                if $alphabet::NORMALIZATION.is_strict() {
+                    assert_eq!(sym.to_string(), input);
+                } else {
+                    assert_eq!(Sym::try_from_str_strict(&sym.to_string()), Ok(sym));
+                }
+            }
+        }
+        $(
+            assert_eq!(
+                s!($ident, $alphabet),
+                Sym::try_from(stringify!($ident)).unwrap()
+            );
+        )*
+    }};
+}
+
+#[test]
+fn test_const_and_runtime_parsing_agree() {
+    cross_check!(
+        DefaultAlphabet,
+        ["hello_world", "Hello", "abcdefghijklmnopqrstuvwxyz_"],
+        [a, __, hello_world, abcdefghijklmnopqrstuvwxyz]
+    );
+    cross_check!(
+        Ferris,
+        ["東京東京東京東京東京東京東京東京東京東京東", "東京_東京-"],
+        [
+            東京,
+            hello東_world京_33,
+            東京東京東京東京東京東京東京東京東京東京東
+        ]
+    );
+    cross_check!(
+        Short,
+        ["hello_world_hello_world"],
+        [hello_world, we_hold_world]
+    );
+    cross_check!(
+        Kanji,
+        ["東京", "大阪", "京都"],
+        [東, 東京, 大阪, 阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪阪]
+    );
+
+    // every alphabet the macros know about, using the macro-time validation
+    cross_check!(
+        smol_symbol::DefaultAlphabet,
+        ["hello_world", "Hello", "abcdefghijklmnopqrstuvwxyz_"],
+        [a, __, hello_world, abcdefghijklmnopqrstuvwxyz]
+    );
+    cross_check!(
+        smol_symbol::AlphaNumeric,
+        ["Hello42", "hello_world", "ZZZzzz999"],
+        [Hello42, ABCDEFGHIJKLMNOPQRSTU, z0]
+    );
+    cross_check!(
+        smol_symbol::Base32Crockford,
+        [
+            "0123456789ABCDEFGHJKMNPQRSTV",
+            "oIl",
+            "deadbeef",
+            "DEADBEEF",
+            "U",
+            "0-1"
+        ],
+        [oIl, OIL, deadbeef, DeadBeef, ABCDEFGHJKMNPQRSTVWXYZ, z9]
+    );
+    cross_check!(
+        smol_symbol::Hex,
+        ["deadbeef", "DEADBEEF", "cafe-babe", "0123456789abcdef"],
+        [deadbeef, a0, f0f0f0f0f0f0f0f0f0f0f0f0f0f0f0f]
+    );
+    cross_check!(
+        smol_symbol::UpperSnake,
+        ["MAX_VALUE_2", "Not_Upper", "A-B"],
+        [MAX_VALUE_2, A, ___9]
+    );
+    cross_check!(
+        smol_symbol::Kebab,
+        ["kebab-case-1", "snake_case", "Kebab"],
+        [kebab, a1]
+    );
+    cross_check!(
+        smol_symbol::DottedPath,
+        ["config.server.port_2", "a-b", "A.B"],
+        [config, port_2]
+    );
+}
+
+#[test]
+fn test_from_str() {
+    assert_eq!("hello_world".parse::<Symbol>(), Ok(s!(hello_world)));
+    assert_eq!(
+        "hello world".parse::<Symbol>(),
+        Err(SymbolParsingError::InvalidChar { ch: ' ', index: 5 })
+    );
+
+    // symbols can be created from string expressions in const contexts
+    const PREFIXED: Symbol = DefaultAlphabet::parse_str_panic(concat!("my_", "prefix"));
+    assert_eq!(PREFIXED, s!(my_prefix));
+    const PKG: core::result::Result<Symbol, SymbolParsingError> =
+        Symbol::try_from_str(env!("CARGO_PKG_NAME"));
+    assert_eq!(
+        PKG,
+        Err(SymbolParsingError::InvalidChar { ch: '-', index: 4 })
+    );
+    const FERRIS: CustomSymbol<{ Ferris::LEN }, Ferris> = Ferris::parse_str_panic("東京_Ferris");
+    assert_eq!(FERRIS, s!(東京_Ferris, Ferris));
+    assert_eq!(
+        Kanji::parse_str("東京x"),
+        Err(SymbolParsingError::InvalidChar { ch: 'x', index: 2 })
+    );
+}
+
+custom_alphabet!(LowerAlnum, abcdefghijklmnopqrstuvwxyz0123456789);
+
+#[test]
+fn test_symbol_literals() {
+    // keywords can be used via raw idents, string literals, or directly
+    assert_eq!(s!(r#type).to_string(), "type");
+    assert_eq!(s!("type"), s!(r#type));
+    assert_eq!(s!(match), Symbol::try_from("match").unwrap());
+    assert_eq!(s!(self).to_string(), "self");
+    assert_eq!(s!("self"), s!(self));
+
+    // string literals can contain characters that aren't valid in idents
+    assert_eq!(s!("hello_world"), s!(hello_world));
+    assert_eq!(s!("123abc", LowerAlnum).to_string(), "123abc");
+    assert_eq!(s!(r#abc123, LowerAlnum), s!("abc123", LowerAlnum));
+    const NUMERIC: CustomSymbol<{ LowerAlnum::LEN }, LowerAlnum> = s!("42", LowerAlnum);
+    assert_eq!(NUMERIC.to_string(), "42");
+    assert_eq!(s!("東京", Kanji), Kanji::parse_str_panic("東京"));
+}
+
+#[docify::export]
+#[test]
+fn sym_match_example() {
+    fn command(sym: Symbol, verbose: bool) -> &'static str {
+        sym_match!(sym {
+            start | begin => "starting",
+            "stop" => "stopping",
+            r#type if verbose => "typing verbosely",
+            r#type => "typing",
+            _ => "unknown",
+        })
+    }
+    assert_eq!(command(s!(start), false), "starting");
+    assert_eq!(command(s!(begin), false), "starting");
+    assert_eq!(command(s!(stop), false), "stopping");
+    assert_eq!(command(s!(type), true), "typing verbosely");
+    assert_eq!(command(s!(type), false), "typing");
+    assert_eq!(command(s!(other), false), "unknown");
+
+    // the fallback arm can bind the unmatched symbol, and custom alphabets are supported
+    let describe = |sym| {
+        sym_match!(sym, Ferris {
+            Hello | hello => String::from("greeting"),
+            "東京" => String::from("city"),
+            other @ _ if other.to_string().len() > 10 => String::from("long"),
+            other @ _ => format!("unknown: {other}"),
+        })
+    };
+    assert_eq!(describe(s!(Hello, Ferris)), "greeting");
+    assert_eq!(describe(s!(東京, Ferris)), "city");
+    assert_eq!(describe(s!(東京東京, Ferris)), "long");
+    assert_eq!(describe(s!(Ferris, Ferris)), "unknown: Ferris");
+
+    // a lone fallback arm is fine too
+    assert_eq!(sym_match!(s!(anything) { _ => 1 }), 1);
+}
+
+custom_alphabet!(Compact, abcdefghijklmnopqrstuvwxyz_, u64);
+
+#[test]
+fn test_symbol_reprs() {
+    // each character takes log2(28) ~= 4.8 bits
+    assert_eq!(CustomSymbol::<27, DefaultAlphabet, u32>::MAX_SYMBOL_LEN, 6);
+    assert_eq!(CustomSymbol::<27, DefaultAlphabet, u64>::MAX_SYMBOL_LEN, 13);
+    assert_eq!(
+        CustomSymbol::<27, DefaultAlphabet, u128>::MAX_SYMBOL_LEN,
+        26
+    );
+    assert_eq!(
+        CustomSymbol::<27, DefaultAlphabet, U256>::MAX_SYMBOL_LEN,
+        53
+    );
+    assert_eq!(Compact::MAX_SYMBOL_LEN, 13);
+    assert_eq!(core::mem::size_of::<CustomSymbol<27, Compact>>(), 8);
+    assert_eq!(
+        core::mem::size_of::<CustomSymbol<27, DefaultAlphabet, U256>>(),
+        32
+    );
+
+    // the alphabet's `Repr` is used by default, but can be overridden
+    let compact: CustomSymbol<27, Compact> = s!(thirteen_char, Compact);
+    assert_eq!(compact.to_string(), "thirteen_char");
+    assert_eq!(
+        CustomSymbol::<27, Compact>::try_from("thirteen_char").unwrap(),
+        compact
+    );
+    assert_eq!(
+        CustomSymbol::<27, Compact>::try_from("fourteen_chars"),
+        Err(SymbolParsingError::TooLong { len: 14, max: 13 })
+    );
+    let small = s!(hello, DefaultAlphabet, u32);
+    assert_eq!(u32::from(small) as u128, u128::from(s!(hello)));
+    assert_eq!(small.inline_name(), "hello");
+
+    // `SymbolName`s are only as large as the symbol's `SymbolRepr` requires
+    assert_eq!(
+        core::mem::size_of::<SymbolName<27, DefaultAlphabet, u32>>(),
+        core::mem::size_of::<usize>() * 2
+    );
+    assert_eq!(
+        core::mem::size_of::<SymbolName<27, Compact>>(),
+        core::mem::size_of::<usize>() * 3
+    );
+    let raw = CustomSymbol::<27, DefaultAlphabet, u32>::from_raw_unchecked(u32::MAX);
+    assert_eq!(raw.inline_name().len(), raw.chars().count());
+
+    // 256-bit symbols allow for much longer names
+    const LONG: CustomSymbol<27, DefaultAlphabet, U256> = s!(
+        this_is_a_very_long_symbol_that_needs_two_whole_words,
+        DefaultAlphabet,
+        U256
+    );
+    assert_eq!(
+        LONG.name(),
+        "this_is_a_very_long_symbol_that_needs_two_whole_words"
+    );
+    assert_eq!(
+        "this_is_a_very_long_symbol_that_needs_two_whole_words"
+            .parse::<CustomSymbol<27, DefaultAlphabet, U256>>()
+            .unwrap(),
+        LONG
+    );
+    assert_eq!(LONG.inline_name().as_str(), LONG.name());
+    assert!(U256::from(s!(zz, DefaultAlphabet, U256)) > U256::from(s!(z, DefaultAlphabet, U256)));
+
+    // `sym_match!` uses the alphabet's `Repr`, or the one specified
+    let matched = sym_match!(compact, Compact {
+        thirteen_char => 1,
+        _ => 0,
+    });
+    assert_eq!(matched, 1);
+    let matched = sym_match!(LONG, DefaultAlphabet, U256 {
+        short => 1,
+        this_is_a_very_long_symbol_that_needs_two_whole_words => 2,
+        _ => 0,
+    });
+    assert_eq!(matched, 2);
+}
+
+#[derive(Symbolic, Copy, Clone, PartialEq, Eq, Debug)]
+enum Opcode {
+    Start,
+    StopAll,
+    HTTPRequest,
+    #[symbol(rename = "kill")]
+    Terminate,
+    r#Type,
+}
+
+#[derive(Symbolic, PartialEq, Debug)]
+#[symbol(alphabet = LowerAlnum)]
+enum Version {
+    V1,
+    Beta2,
+}
+
+#[test]
+fn test_derive_symbolic() {
+    assert_eq!(Opcode::Start.to_symbol(), s!(start));
+    assert_eq!(Opcode::StopAll.to_symbol(), s!(stop_all));
+    assert_eq!(Opcode::HTTPRequest.to_symbol(), s!(http_request));
+    assert_eq!(Opcode::Terminate.to_symbol(), s!(kill));
+    assert_eq!(Opcode::Type.to_symbol(), s!(type));
+    assert_eq!(
+        Opcode::SYMBOLS,
+        [
+            s!(start),
+            s!(stop_all),
+            s!(http_request),
+            s!(kill),
+            s!(type)
+        ]
+    );
+
+    // conversions work in both directions, and in const contexts
+    const STOP: Symbol = Opcode::StopAll.to_symbol();
+    const KILL: Option<Opcode> = Opcode::from_symbol(s!(kill));
+    assert_eq!(STOP, s!(stop_all));
+    assert_eq!(KILL, Some(Opcode::Terminate));
+    assert_eq!(Opcode::from_symbol(s!(terminate)), None);
+    let sym: Symbol = Opcode::Start.into();
+    assert_eq!(Opcode::try_from(sym), Ok(Opcode::Start));
+    assert_eq!(Opcode::try_from(s!(unknown)), Err(s!(unknown)));
+
+    // custom alphabets are supported
+    let sym: CustomSymbol<{ LowerAlnum::LEN }, LowerAlnum> = Version::Beta2.into();
+    assert_eq!(sym, s!(beta2, LowerAlnum));
+    assert_eq!(Version::try_from(s!(v1, LowerAlnum)), Ok(Version::V1));
+}
+
+#[test]
+fn test_symbol_tables() {
+    // large enough to need several buckets, and to exercise the displacement search
+    static BIG: SymbolMap<usize> = symbol_map! {
+        a => 0, b => 1, c => 2, d => 3, e => 4, f => 5, g => 6, h => 7, i => 8, j => 9,
+        k => 10, l => 11, m => 12, n => 13, o => 14, p => 15, q => 16, r => 17, s => 18,
+        t => 19, u => 20, v => 21, w => 22, x => 23, y => 24, z => 25, aa => 26, ab => 27,
+        ac => 28, ad => 29, ae => 30, af => 31, ag => 32, ah => 33, ai => 34, aj => 35,
+        ak => 36, al => 37, am => 38, an => 39, ao => 40, ap => 41, aq => 42, ar => 43,
+        as_ => 44, at => 45, au => 46, av => 47, aw => 48, ax => 49,
+    };
+    assert_eq!(BIG.len(), 50);
+    for (i, (key, value)) in BIG.iter().enumerate() {
+        assert_eq!(*value, i);
+        assert_eq!(BIG.get(key), Some(&i));
+        assert_eq!(BIG.get_key_value(key), Some((key, &i)));
+    }
+    assert_eq!(BIG.get(&s!(ay)), None);
+    assert_eq!(BIG.get(&s!(b_)), None);
+
+    // empty tables are allowed
+    static EMPTY: SymbolMap<u8> = symbol_map! {};
+    assert!(EMPTY.is_empty());
+    assert_eq!(EMPTY.get(&s!(a)), None);
+    const EMPTY_SET: SymbolSet = symbol_set! {};
+    assert!(!EMPTY_SET.contains(&s!(a)));
+
+    // custom alphabets and reprs are supported
+    static CITIES: SymbolMap<&str, CustomSymbol<{ Ferris::LEN }, Ferris>> = symbol_map!(Ferris {
+        "東京" => "Tokyo",
+        Paris => "Paris",
+    });
+    assert_eq!(CITIES.get(&s!(東京, Ferris)), Some(&"Tokyo"));
+    assert_eq!(CITIES.get(&s!(Tokyo, Ferris)), None);
+    static LONG: SymbolSet<CustomSymbol<27, DefaultAlphabet, U256>> = symbol_set!(
+        DefaultAlphabet,
+        U256 {
+            short,
+            this_is_a_very_long_symbol_that_needs_two_whole_words,
+        }
+    );
+    assert!(LONG.contains(&s!(
+        this_is_a_very_long_symbol_that_needs_two_whole_words,
+        DefaultAlphabet,
+        U256
+    )));
+    assert!(!LONG.contains(&s!(long, DefaultAlphabet, U256)));
+    let compact = symbol_set!(Compact { thirteen_char });
+    assert!(compact.contains(&s!(thirteen_char, Compact)));
+
+    // values can be arbitrary constant expressions
+    const MAP: SymbolMap<usize> = symbol_map! { one => [1].len(), two => { 1 + 1 } };
+    let map = MAP;
+    assert_eq!(map.get(&s!(two)), Some(&2));
+    assert_eq!(
+        format!("{map:?}"),
+        format!("{{{:?}: 1, {:?}: 2}}", s!(one), s!(two))
+    );
+    assert_eq!(
+        format!("{:?}", symbol_set! { one }),
+        format!("{{{:?}}}", s!(one))
+    );
+}
+
+#[test]
+fn test_symbol_hasher() {
+    use core::hash::{BuildHasher, Hash, Hasher};
+    let hash = |sym: &dyn Fn(&mut SymbolHasher)| {
+        let mut hasher = SymbolBuildHasher.build_hasher();
+        sym(&mut hasher);
+        hasher.finish()
+    };
+    let hash_symbol = |sym: Symbol| hash(&|hasher| sym.hash(hasher));
+    assert_eq!(hash_symbol(s!(hello)), hash_symbol(s!(hello)));
+    assert_ne!(hash_symbol(s!(hello)), hash_symbol(s!(hellp)));
+    assert_ne!(hash_symbol(s!(a)), hash_symbol(s!(b)));
+    // both halves of the backing `u128` contribute to the hash
+    assert_ne!(
+        hash(&|hasher| hasher.write_u128(1 << 64)),
+        hash(&|hasher| hasher.write_u128(2 << 64))
+    );
+    // including when the high half equals the multiplier used internally by the hasher
+    const MULTIPLE: u128 = 0x243f_6a88_85a3_08d3;
+    assert_ne!(
+        hash(&|hasher| hasher.write_u128(MULTIPLE << 64 | 1)),
+        hash(&|hasher| hasher.write_u128(MULTIPLE << 64 | 2))
+    );
+    // trailing zero bytes are not ignored
+    assert_ne!(
+        hash(&|hasher| hasher.write(&[1])),
+        hash(&|hasher| hasher.write(&[1, 0]))
+    );
+
+    #[cfg(feature = "std")]
+    {
+        let mut map = SymbolHashMap::default();
+        map.insert(s!(hello), 1);
+        map.insert(s!(world), 2);
+        assert_eq!(map.get(&s!(hello)), Some(&1));
+        assert_eq!(map.get(&s!(other)), None);
+        let mut set: SymbolHashSet<CustomSymbol<{ Compact::LEN }, Compact>> =
+            SymbolHashSet::default();
+        assert!(set.insert(s!(hello, Compact)));
+        assert!(!set.insert(s!(hello, Compact)));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_interned_symbols() {
+    use std::collections::HashSet;
+    let names = [
+        "short",
+        "Not_In_Alphabet",
+        "a_name_that_is_much_too_long_for_a_symbol",
+    ];
+    // interning the same names from several threads at once yields the same symbols
+    let threads: Vec<_> = (0..8)
+        .map(|_| std::thread::spawn(move || names.map(InternedSymbol::new)))
+        .collect();
+    let results: Vec<_> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+    for result in &results {
+        assert_eq!(result, &results[0]);
+    }
+    let [short, upper, long] = results[0];
+    assert!(short.is_inline());
+    assert_eq!(short.as_interned_str(), None);
+    assert_eq!(upper.as_interned_str(), Some("Not_In_Alphabet"));
+    assert_eq!(long.to_symbol(), None);
+    assert_eq!(long.to_string(), names[2]);
+    assert_eq!(format!("{short}"), "short");
+    assert_eq!(
+        format!("{upper:?}"),
+        "InternedSymbol { inline: false, symbol: \"Not_In_Alphabet\" }"
+    );
+
+    // `String`s, `FromStr` and custom alphabets are supported too
+    let from_string =
+        InternedSymbol::from(String::from("a_name_that_is_much_too_long_for_a_symbol"));
+    assert_eq!(from_string, long);
+    assert_eq!("short".parse::<InternedSymbol>(), Ok(short));
+    let set: HashSet<_> = [short, upper, long, from_string].into_iter().collect();
+    assert_eq!(set.len(), 3);
+    let ferris = CustomInternedSymbol::<{ Ferris::LEN }, Ferris>::new("東京");
+    assert_eq!(ferris.to_symbol(), Some(s!(東京, Ferris)));
+    let empty = InternedSymbol::new("");
+    assert!(!empty.is_inline());
+    assert_eq!(empty.name(), "");
+
+    // names are never normalized, so ones that would be changed by it are interned instead
+    type CliInterned = CustomInternedSymbol<{ Cli::LEN }, Cli>;
+    let normalized = CliInterned::new("Hello-World");
+    assert!(!normalized.is_inline());
+    assert_eq!(normalized.name(), "Hello-World");
+    assert_eq!(CliInterned::from(String::from("Hello-World")), normalized);
+    assert_ne!(normalized, CliInterned::new("hello_world"));
+    assert_eq!(
+        CliInterned::new("hello_world").to_symbol(),
+        Some(s!("Hello-World", Cli))
+    );
+    assert!(!CliInterned::new(" hello").is_inline());
+}
+
+custom_alphabet!(
+    Cli,
+    abcdefghijklmnopqrstuvwxyz_,
+    normalize(fold_case, '-' | ' ' => '_', trim)
+);
+custom_alphabet!(CaseInsensitive, abcdefghij, u64, normalize(fold_case));
+
+#[test]
+fn test_normalization() {
+    type CliSymbol = CustomSymbol<{ Cli::LEN }, Cli>;
+    assert_eq!(DefaultAlphabet::NORMALIZATION, Normalization::STRICT);
+    assert!(!Cli::NORMALIZATION.is_strict());
+    assert!(Cli::NORMALIZATION.aliases.contains(&('H', 'h')));
+
+    // normalization is applied consistently by `s!`, `TryFrom`, `FromStr` and `try_from_str`
+    let canonical = s!(hello_world, Cli);
+    assert_eq!(s!("Hello-World", Cli), canonical);
+    assert_eq!(s!(HELLO_WORLD, Cli), canonical);
+    assert_eq!(CliSymbol::try_from("  hello world\n"), Ok(canonical));
+    assert_eq!(" Hello-World ".parse::<CliSymbol>(), Ok(canonical));
+    const PARSED: CliSymbol = Cli::parse_str_panic("\tHELLO world ");
+    assert_eq!(PARSED, canonical);
+    assert_eq!(Cli::invert_char('W'), Cli::invert_char('w'));
+    assert_eq!(<Cli as Alphabet<27>>::invert_char('-'), Ok(27));
+
+    // symbols always decode to their canonical name
+    assert_eq!(s!("Hello-World", Cli).to_string(), "hello_world");
+
+    // strict parsing retains the exact behavior of non-normalizing alphabets
+    assert_eq!(Cli::parse_str_strict("hello_world"), Ok(canonical));
+    assert_eq!(
+        Cli::parse_str_strict("hello-world"),
+        Err(SymbolParsingError::InvalidChar { ch: '-', index: 5 })
+    );
+    assert_eq!(
+        CliSymbol::try_from_str_strict(" hello"),
+        Err(SymbolParsingError::InvalidChar { ch: ' ', index: 0 })
+    );
+    assert_eq!(Symbol::try_from_str_strict("hello"), Ok(s!(hello)));
+    assert!(Symbol::try_from("Hello").is_err());
+
+    // error positions refer to the untrimmed input, and whitespace alone is empty
+    assert_eq!(
+        CliSymbol::try_from("  ab?"),
+        Err(SymbolParsingError::InvalidChar { ch: '?', index: 4 })
+    );
+    assert_eq!(
+        CliSymbol::try_from_str("  ab?"),
+        Err(SymbolParsingError::InvalidChar { ch: '?', index: 4 })
+    );
+    assert_eq!(
+        CliSymbol::try_from_chars(&[' ', 'a', '?']),
+        Err(SymbolParsingError::InvalidChar { ch: '?', index: 2 })
+    );
+    assert_eq!(CliSymbol::try_from("   "), Err(SymbolParsingError::Empty));
+    assert_eq!(
+        CliSymbol::try_from_str("   "),
+        Err(SymbolParsingError::Empty)
+    );
+
+    // only the specified rules are applied
+    let sym: CustomSymbol<{ CaseInsensitive::LEN }, CaseInsensitive> = "BAD".parse().unwrap();
+    assert_eq!(sym, s!(bad, CaseInsensitive));
+    assert!(" bad".parse::<CustomSymbol<10, CaseInsensitive>>().is_err());
+    assert!("b-a".parse::<CustomSymbol<10, CaseInsensitive>>().is_err());
+}
+
+custom_alphabet!(Path, ['a'..='z', '0'..='9', '-', '.', '/']);
+custom_alphabet!(
+    /// Alphabets can have doc comments.
+    Mixed,
+    ["xyz", 'a'..='c', '!']
+);
+
+#[test]
+fn test_alphabet_ranges() {
+    assert_eq!(Path::LEN, 39);
+    assert_eq!(&Path::ALPHABET[24..28], ['y', 'z', '0', '1']);
+    assert_eq!(Path::ALPHABET[36..], ['-', '.', '/']);
+    assert_eq!(Mixed::ALPHABET, ['x', 'y', 'z', 'a', 'b', 'c', '!']);
+    let path = s!("usr/lib/libc.so.6", Path);
+    assert_eq!(path.to_string(), "usr/lib/libc.so.6");
+    assert_eq!("usr/lib/libc.so.6".parse(), Ok(path));
+    assert_eq!(s!("ab!", Mixed).to_string(), "ab!");
+}
+
+#[test]
+fn test_predefined_alphabets() {
+    assert_eq!(AlphaNumeric::MAX_SYMBOL_LEN, 21);
+    assert_eq!(Base32Crockford::MAX_SYMBOL_LEN, 25);
+    assert_eq!(Hex::MAX_SYMBOL_LEN, 31);
+    assert_eq!(UpperSnake::MAX_SYMBOL_LEN, 24);
+    assert_eq!(Kebab::MAX_SYMBOL_LEN, 24);
+    assert_eq!(DottedPath::MAX_SYMBOL_LEN, 24);
+
+    assert_eq!(s!(Hello42, AlphaNumeric).to_string(), "Hello42");
+    assert_eq!(s!("deadbeef", Hex).to_string(), "deadbeef");
+    assert!(Hex::parse_str("DEADBEEF").is_err());
+    assert_eq!(s!(MAX_VALUE_2, UpperSnake).to_string(), "MAX_VALUE_2");
+    assert_eq!(s!("kebab-case-1", Kebab).to_string(), "kebab-case-1");
+    assert_eq!(
+        s!("config.server.port_2", DottedPath).to_string(),
+        "config.server.port_2"
+    );
+
+    // Crockford's Base32 is case-insensitive, and reads I / L as 1 and O as 0
+    let id = s!("0123456789ABCDEFGHJKMNPQRSTVWXYZ", Base32Crockford, U256);
+    assert_eq!(id.to_string(), "0123456789ABCDEFGHJKMNPQRSTVWXYZ");
+    assert_eq!(s!(abc, Base32Crockford), s!(ABC, Base32Crockford));
+    assert_eq!(s!("Oil", Base32Crockford), s!("011", Base32Crockford));
+    assert_eq!(
+        "oIl".parse::<CustomSymbol<32, Base32Crockford>>(),
+        Ok(s!("011", Base32Crockford))
+    );
+    assert!(Base32Crockford::parse_str("U").is_err());
+}
+
+/// User-defined alphabets may share a name with one shipped with `smol-symbol`, in which
+/// case the macros must not validate symbols against the latter.
+mod shadowed_alphabets {
+    use super::*;
+
+    custom_alphabet!(Hex, ['0'..='9', 'A'..='F']);
+    custom_alphabet!(Kebab, ['a'..='z', '0'..='9', '-', '_']);
+
+    #[derive(Symbolic, Copy, Clone, PartialEq, Eq, Debug)]
+    #[symbol(alphabet = Hex)]
+    enum Marker {
+        #[symbol(rename = "DEAD")]
+        Dead,
+        #[symbol(rename = "BEEF")]
+        Beef,
+    }
+
+    #[test]
+    fn test_shadowed_alphabets() {
+        let dead = s!(DEAD, Hex);
+        assert_eq!(dead.to_string(), "DEAD");
+        assert_eq!(s!("C0FFEE", Hex).to_string(), "C0FFEE");
+        assert!(Hex::parse_str("dead").is_err());
+        assert_eq!(s!(dead, smol_symbol::Hex).to_string(), "dead");
+
+        let matched = sym_match!(dead, Hex {
+            BEEF => 1,
+            DEAD => 2,
+            _ => 0,
+        });
+        assert_eq!(matched, 2);
+
+        static MARKERS: SymbolMap<u8, CustomSymbol<{ Hex::LEN }, Hex>> = symbol_map!(Hex {
+            DEAD => 1,
+            BEEF => 2,
+        });
+        assert_eq!(MARKERS.get(&dead), Some(&1));
+        let set = symbol_set!(Hex { DEAD, BEEF });
+        assert!(set.contains(&s!(BEEF, Hex)));
+
+        assert_eq!(Marker::Dead.to_symbol(), dead);
+        assert_eq!(Marker::try_from(s!(BEEF, Hex)), Ok(Marker::Beef));
+
+        // conversions to and from the built-in alphabet are still generated
+        let kebab: CustomSymbol<{ Kebab::LEN }, Kebab> =
+            s!("kebab-case", smol_symbol::Kebab).into();
+        assert_eq!(kebab, s!("kebab-case", Kebab));
+        assert_eq!(s!(snake_case, Kebab).to_string(), "snake_case");
+    }
+}
+
+#[test]
+fn test_char_accessors() {
+    let sym = s!(hello_world);
+    assert_eq!(sym.len(), 11);
+    assert!(!sym.is_empty());
+    assert_eq!(sym.chars().collect::<String>(), "hello_world");
+    assert_eq!(sym.chars().count(), sym.len());
+    assert_eq!(sym.char_at(0), Some('h'));
+    assert_eq!(sym.char_at(5), Some('_'));
+    assert_eq!(sym.char_at(10), Some('d'));
+    assert_eq!(sym.char_at(11), None);
+    assert_eq!(sym.char_at(usize::MAX), None);
+    assert_eq!(sym.first(), Some('h'));
+    assert_eq!(sym.last(), Some('d'));
+
+    let single = s!(a);
+    assert_eq!(single.len(), 1);
+    assert_eq!(single.first(), single.last());
+
+    let empty = Symbol::from_raw_unchecked(0);
+    assert!(empty.is_empty());
+    assert_eq!(empty.len(), 0);
+    assert_eq!(empty.chars().next(), None);
+    assert_eq!(empty.char_at(0), None);
+    assert_eq!(empty.first(), None);
+    assert_eq!(empty.last(), None);
+
+    // all of these are also available in const contexts, for every repr
+    const LONG: CustomSymbol<{ Ferris::LEN }, Ferris, U256> = s!(東京_Ferris, Ferris, U256);
+    const LEN: usize = LONG.len();
+    const FIRST: Option<char> = LONG.first();
+    const LAST: Option<char> = LONG.last();
+    const THIRD: Option<char> = LONG.char_at(2);
+    assert_eq!(LEN, 9);
+    assert_eq!(FIRST, Some('東'));
+    assert_eq!(LAST, Some('s'));
+    assert_eq!(THIRD, Some('_'));
+    assert_eq!(LONG.chars().collect::<String>(), "東京_Ferris");
+
+    let short = s!(hi, DefaultAlphabet, u32);
+    assert_eq!(short.len(), 2);
+    assert_eq!(format!("{:?}", short.chars()), "['h', 'i']");
+}
+
+#[test]
+fn test_raw_validation() {
+    let sym = s!(hello_world);
+    assert_eq!(Symbol::try_from_raw(sym.to_raw()), Ok(sym));
+    assert_eq!(Symbol::try_from(u128::from(sym)), Ok(sym));
+    assert_eq!(Symbol::try_from_raw(0), Err(SymbolParsingError::Empty));
+    // the old name still works, but is deprecated
+    #[allow(deprecated)]
+    let unchecked = Symbol::from_raw(sym.to_raw());
+    assert_eq!(unchecked, sym);
+
+    // a zero digit anywhere below the most significant digit doesn't decode to a char
+    assert_eq!(
+        Symbol::try_from_raw(28),
+        Err(SymbolParsingError::InvalidRaw)
+    );
+    assert_eq!(
+        Symbol::try_from_raw(sym.to_raw() * 28),
+        Err(SymbolParsingError::InvalidRaw)
+    );
+    assert_eq!(
+        Symbol::try_from_raw(29).map(|s| s.to_string()),
+        Ok("aa".into())
+    );
+
+    // 27 non-zero digits fit in a `u128`, but symbols are limited to 26 characters
+    let mut too_long = 0u128;
+    for _ in 0..27 {
+        too_long = too_long * 28 + 1;
+    }
+    assert_eq!(
+        Symbol::try_from_raw(too_long),
+        Err(SymbolParsingError::TooLong { len: 27, max: 26 })
+    );
+    assert_eq!(Symbol::try_from_raw(too_long / 28).map(|s| s.len()), Ok(26));
+    assert!(Symbol::try_from(u128::MAX).is_err());
+
+    // also works for other reprs and in const contexts
+    const SHORT: CustomSymbol<{ DefaultAlphabet::LEN }, DefaultAlphabet, u32> =
+        match CustomSymbol::<{ DefaultAlphabet::LEN }, DefaultAlphabet, u32>::try_from_raw(29) {
+            Ok(symbol) => symbol,
+            Err(_) => panic!(),
+        };
+    assert_eq!(SHORT, s!(aa, DefaultAlphabet, u32));
+    assert_eq!(
+        CustomSymbol::<{ DefaultAlphabet::LEN }, DefaultAlphabet, u64>::try_from(u64::MAX),
+        Err(SymbolParsingError::TooLong { len: 14, max: 13 })
+    );
+    let wide = s!(hello_world_hello_world_hello_world, DefaultAlphabet, U256);
+    assert_eq!(wide.to_raw().try_into(), Ok(wide));
+    assert_eq!(
+        SymbolParsingError::InvalidRaw.to_string(),
+        SymbolParsingError::InvalidRaw.message()
+    );
+
+    // lex symbols are left-aligned, so only trailing zero digits are allowed
+    let lex = LexSymbol::from(sym);
+    assert_eq!(LexSymbol::try_from_raw(u128::from(lex)), Ok(lex));
+    assert_eq!(LexSymbol::try_from(u128::from(lex)), Ok(lex));
+    assert_eq!(LexSymbol::try_from_raw(0), Err(SymbolParsingError::Empty));
+    assert_eq!(
+        LexSymbol::try_from_raw(1 + 28 * 28),
+        Err(SymbolParsingError::InvalidRaw)
+    );
+    assert_eq!(
+        LexSymbol::try_from_raw(u128::from(lex) / 28),
+        Err(SymbolParsingError::InvalidRaw)
+    );
+    assert_eq!(
+        LexSymbol::try_from_raw(u128::from(lex) + 28 * 28),
+        Err(SymbolParsingError::InvalidRaw)
+    );
+    assert_eq!(
+        LexSymbol::try_from_raw(too_long),
+        Err(SymbolParsingError::TooLong { len: 27, max: 26 })
+    );
+    let longest = LexSymbol::from(Symbol::try_from_raw(too_long / 28).unwrap());
+    assert_eq!(LexSymbol::try_from(u128::from(longest)), Ok(longest));
+    let all = LexSymbol::from_raw_unchecked(0).prefix_range();
+    assert_eq!(
+        LexSymbol::try_from_raw(u128::from(*all.end())),
+        Ok(*all.end())
+    );
+}
+
+#[test]
+fn test_symbol_concatenation() {
+    let user = s!(user);
+    let created = s!(created);
+    let joined = user.concat(s!(_)).and_then(|s| s.concat(created));
+    assert_eq!(joined, Ok(s!(user_created)));
+    assert_eq!(user.push_char('_'), Ok(s!(user_)));
+    assert_eq!(
+        user.push_char('-'),
+        Err(SymbolParsingError::InvalidChar { ch: '-', index: 4 })
+    );
+    assert_eq!(
+        s!(abcdefghijklmnopqrstuvwxyz).push_char('a'),
+        Err(SymbolParsingError::TooLong { len: 27, max: 26 })
+    );
+    assert_eq!(
+        s!(abcdefghijklm).concat(s!(nopqrstuvwxyz_)),
+        Err(SymbolParsingError::TooLong { len: 27, max: 26 })
+    );
+    assert_eq!(
+        s!(abcdefghijklm).concat(s!(nopqrstuvwxyz)),
+        Ok(s!(abcdefghijklmnopqrstuvwxyz))
+    );
+
+    let sym = s!(user_created);
+    assert!(sym.starts_with(s!(user)));
+    assert!(sym.starts_with(sym));
+    assert!(!sym.starts_with(s!(created)));
+    assert!(!s!(us).starts_with(s!(user)));
+    assert!(sym.ends_with(s!(created)));
+    assert!(!sym.ends_with(s!(user)));
+    assert!(!s!(ted).ends_with(s!(created)));
+    assert_eq!(sym.strip_prefix(s!(user_)), Some(s!(created)));
+    assert_eq!(sym.strip_prefix(s!(created)), None);
+    assert_eq!(sym.strip_suffix(s!(_created)), Some(s!(user)));
+    assert_eq!(sym.strip_suffix(s!(user)), None);
+    assert_eq!(sym.strip_prefix(sym), None);
+    assert_eq!(sym.strip_suffix(sym), None);
+
+    assert_eq!(sym.slice(0..4), Some(s!(user)));
+    assert_eq!(sym.slice(5..12), Some(s!(created)));
+    assert_eq!(sym.slice(2..7), Some(s!(er_cr)));
+    assert_eq!(sym.slice(0..12), Some(sym));
+    assert_eq!(sym.slice(3..3), None);
+    assert_eq!(sym.slice(0..13), None);
+    let (start, end) = (5, 4);
+    assert_eq!(sym.slice(start..end), None);
+    assert_eq!(sym.split_at(4), Some((s!(user), s!(_created))));
+    assert_eq!(sym.split_at(13), None);
+    assert_eq!(sym.split_at(12), None);
+    assert_eq!(sym.split_at(0), None);
+    assert_eq!(sym.split_at(11), Some((s!(user_create), s!(d))));
+
+    // compile-time concatenation, and everything above in const contexts for other reprs
+    assert_eq!(s!(user + "_" + created), sym);
+    assert_eq!(s!(東京 + "_" + Ferris, Ferris), s!(東京_Ferris, Ferris));
+    const WIDE: CustomSymbol<{ DefaultAlphabet::LEN }, DefaultAlphabet, U256> =
+        match s!(hello_world_hello_world, DefaultAlphabet, U256).concat(s!(
+            _hello_world,
+            DefaultAlphabet,
+            U256
+        )) {
+            Ok(symbol) => symbol,
+            Err(_) => panic!(),
+        };
+    assert_eq!(
+        WIDE,
+        s!(hello_world_hello_world_hello_world, DefaultAlphabet, U256)
+    );
+    const PREFIX: Option<CustomSymbol<{ DefaultAlphabet::LEN }, DefaultAlphabet, u32>> =
+        s!(hi_you, DefaultAlphabet, u32).slice(0..2);
+    assert_eq!(PREFIX, Some(s!(hi, DefaultAlphabet, u32)));
+}
+
+#[test]
+fn test_prefix_ranges() {
+    use std::collections::BTreeSet;
+
+    let lex = |sym: Symbol| LexSymbol::from(sym);
+    let range = lex(s!(ab)).prefix_range();
+    assert_eq!(*range.start(), lex(s!(ab)));
+    assert_eq!(*range.end(), lex(s!(abzzzzzzzzzzzzzzzzzzzzzzzz)));
+    assert!(range.contains(&lex(s!(ab_))));
+    assert!(range.contains(&lex(s!(abz))));
+    assert!(!range.contains(&lex(s!(a))));
+    assert!(!range.contains(&lex(s!(a_))));
+    assert!(!range.contains(&lex(s!(ac))));
+    assert!(!range.contains(&lex(s!(b))));
+
+    // a symbol of the maximum length is only a prefix of itself
+    let full = lex(s!(abcdefghijklmnopqrstuvwxyz));
+    assert_eq!(full.prefix_range(), full..=full);
+
+    // every symbol starts with the empty symbol
+    let all = LexSymbol::from_raw_unchecked(0).prefix_range();
+    assert!(all.contains(&lex(s!(_))) && all.contains(&full));
+    assert_eq!(*all.end(), lex(s!(zzzzzzzzzzzzzzzzzzzzzzzzzz)));
+
+    // `_` sorts before the letters, so it is the lowest ranked character
+    let set: BTreeSet<LexSymbol> = [s!(a), s!(a_), s!(ab), s!(abc), s!(b), s!(_a)]
+        .into_iter()
+        .map(lex)
+        .collect();
+    let names = |prefix: Symbol| {
+        set.starting_with(prefix)
+            .map(|sym| sym.to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(s!(a)), ["a", "a_", "ab", "abc"]);
+    assert_eq!(names(s!(ab)), ["ab", "abc"]);
+    assert_eq!(names(s!(_)), ["_a"]);
+    assert!(names(s!(c)).is_empty());
+
+    // also works for custom alphabets, whose ranks follow `char` order
+    let ferris: BTreeSet<CustomLexSymbol<{ Ferris::LEN }, Ferris>> = [
+        s!(東京, Ferris),
+        s!(東, Ferris),
+        s!(京, Ferris),
+        s!(A東, Ferris),
+    ]
+    .into_iter()
+    .map(CustomLexSymbol::from)
+    .collect();
+    assert_eq!(ferris.starting_with(s!(東, Ferris)).count(), 2);
+}
+
+custom_alphabet!(Wide, ['a'..='z', '_', '0'..='9', 'A'..='Z', '.'], U256);
+custom_alphabet!(Abc, abc, u32);
+
+#[test]
+fn test_alphabet_conversions() {
+    // `convert` works between any two alphabets, as long as the characters permit
+    assert_eq!(
+        s!(hello).convert::<{ AlphaNumeric::LEN }, AlphaNumeric>(),
+        Ok(s!(hello, AlphaNumeric))
+    );
+    assert_eq!(
+        s!(hello_world).convert::<{ AlphaNumeric::LEN }, AlphaNumeric>(),
+        Err(SymbolParsingError::InvalidChar { ch: '_', index: 5 })
+    );
+    assert_eq!(
+        s!(abcdefghijklmnopqrstuvwxyz).convert::<{ Kebab::LEN }, Kebab>(),
+        Err(SymbolParsingError::TooLong { len: 26, max: 24 })
+    );
+    assert_eq!(
+        s!(東京_Ferris, Ferris).convert::<{ DefaultAlphabet::LEN }, DefaultAlphabet>(),
+        Err(SymbolParsingError::InvalidChar { ch: 'F', index: 3 })
+    );
+    // the normalization rules of the target alphabet apply, just like when parsing a name
+    assert_eq!(
+        s!(Hello, AlphaNumeric).convert::<{ Cli::LEN }, Cli>(),
+        Ok(s!(hello, Cli))
+    );
+    assert_eq!(
+        s!(hello_world, Cli, U256).convert::<{ Wide::LEN }, Wide>(),
+        Ok(s!(hello_world, Wide))
+    );
+    assert!(Symbol::from_raw_unchecked(0)
+        .convert::<{ Wide::LEN }, Wide>()
+        .unwrap()
+        .is_empty());
+
+    // `custom_alphabet!` generates `From` impls when the conversion can never fail
+    let wide: CustomSymbol<{ Wide::LEN }, Wide> = s!(hello_world).into();
+    assert_eq!(wide, s!(hello_world, Wide));
+    assert_eq!(
+        CustomSymbol::<{ Wide::LEN }, Wide>::from(s!(Hello42, AlphaNumeric)),
+        s!(Hello42, Wide)
+    );
+    assert_eq!(
+        CustomSymbol::<{ Wide::LEN }, Wide>::from(s!("config.port_2", DottedPath)),
+        s!("config.port_2", Wide)
+    );
+    assert_eq!(Symbol::from(s!(cab, Abc)), s!(cab));
+    assert_eq!(
+        CustomSymbol::<{ Hex::LEN }, Hex>::from(s!(cab, Abc)),
+        s!(cab, Hex)
+    );
+    assert_eq!(Symbol::from(s!(compact, Compact)), s!(compact));
+    // alphabets with the same characters and maximum length convert both ways
+    assert_eq!(Symbol::from(s!(hello_world, Cli)), s!(hello_world));
+    assert_eq!(
+        CustomSymbol::<{ Cli::LEN }, Cli>::from(s!(hello_world)),
+        s!(hello_world, Cli)
+    );
+}
+
+#[test]
+fn test_dyn_symbols() {
+    use std::collections::HashMap;
+
+    let sym: DynSymbol = s!(hello_world).into();
+    assert!(sym.is::<27, DefaultAlphabet>());
+    assert!(!sym.is::<{ Cli::LEN }, Cli>());
+    assert_eq!(sym.to_raw(), s!(hello_world).to_raw());
+    assert_eq!(sym.alphabet(), DynAlphabet::of::<27, DefaultAlphabet>());
+    assert_eq!(sym.alphabet().chars(), DefaultAlphabet::ALPHABET);
+    // type names are only meant for diagnostics, so their exact format isn't guaranteed
+    assert!(sym.alphabet().name().ends_with("DefaultAlphabet"));
+    assert!(format!("{sym:?}").ends_with("symbol: \"hello_world\" }"));
+    assert_eq!(sym.chars().collect::<String>(), "hello_world");
+
+    // alphabets with the same characters are still distinct
+    let cli: DynSymbol = s!(hello_world, Cli).into();
+    assert_eq!(cli.to_raw(), sym.to_raw());
+    assert_ne!(cli, sym);
+    assert_eq!(
+        cli.downcast::<{ Cli::LEN }, Cli>(),
+        Some(s!(hello_world, Cli))
+    );
+    assert_eq!(cli.downcast::<27, DefaultAlphabet>(), None);
+
+    // the encoding doesn't depend on the repr, so symbols backed by different reprs of the
+    // same alphabet are equal, and can be downcast as long as they fit the default repr
+    let narrow: DynSymbol = s!(hello, DefaultAlphabet, u32).into();
+    assert_eq!(narrow, DynSymbol::from(s!(hello)));
+    assert_eq!(narrow.downcast::<27, DefaultAlphabet>(), Some(s!(hello)));
+    let compact: DynSymbol = s!(hello_world, Compact).into();
+    assert_eq!(
+        compact.downcast::<27, Compact>(),
+        Some(s!(hello_world, Compact))
+    );
+    let long: DynSymbol = s!(hello_world_hello, Compact, u128).into();
+    assert_eq!(long.to_string(), "hello_world_hello");
+    assert_eq!(long.downcast::<27, Compact>(), None);
+
+    let mut map = HashMap::new();
+    map.insert(sym, 1);
+    map.insert(cli, 2);
+    map.insert(DynSymbol::from(s!(東京, Ferris)), 3);
+    assert_eq!(map[&DynSymbol::from(s!(hello_world))], 1);
+    assert_eq!(map[&DynSymbol::from(s!(hello_world, Cli))], 2);
+    assert_eq!(map[&DynSymbol::from(s!(東京, Ferris))], 3);
+    assert_eq!(DynSymbol::from(s!(東京, Ferris)).to_string(), "東京");
+}
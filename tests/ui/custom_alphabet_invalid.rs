@@ -0,0 +1,11 @@
+use smol_symbol::*;
+
+custom_alphabet!(Duplicates, hello_world);
+
+custom_alphabet!(Empty, []);
+
+custom_alphabet!(EmptyString, [""]);
+
+custom_alphabet!(DedupTwice, abc, dedup, dedup);
+
+fn main() {}
@@ -0,0 +1,11 @@
+use smol_symbol::*;
+
+custom_alphabet!(ReplacesMember, abc_, normalize('a' => '_'));
+
+custom_alphabet!(ReplacesWithNonMember, abc, normalize('-' => '_'));
+
+custom_alphabet!(UnknownRule, abc, normalize(uppercase));
+
+custom_alphabet!(Pointless, abcABC, normalize(fold_case));
+
+fn main() {}
@@ -0,0 +1,14 @@
+use smol_symbol::*;
+
+custom_alphabet!(Overlapping, ['a'..='f', 'e'..='z']);
+
+custom_alphabet!(Repeated, ['a', "bca"]);
+
+custom_alphabet!(Backwards, ['z'..='a']);
+
+custom_alphabet!(Exclusive, ['a'..'z']);
+
+fn main() {
+    let _ = s!("cafe-babe", smol_symbol::Hex);
+    let _ = s!("Not_Upper", smol_symbol::UpperSnake);
+}
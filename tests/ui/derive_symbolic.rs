@@ -0,0 +1,29 @@
+use smol_symbol::*;
+
+#[derive(Symbolic)]
+enum InvalidChar {
+    Http2,
+}
+
+#[derive(Symbolic)]
+enum Duplicate {
+    Start,
+    #[symbol(rename = "start")]
+    Begin,
+}
+
+#[derive(Symbolic)]
+enum NotUnit {
+    Value(u32),
+}
+
+#[derive(Symbolic)]
+#[symbol(rename = "nope")]
+enum UnknownAttribute {
+    A,
+}
+
+#[derive(Symbolic)]
+struct NotAnEnum;
+
+fn main() {}
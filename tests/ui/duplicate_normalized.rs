@@ -0,0 +1,21 @@
+use smol_symbol::*;
+
+#[derive(Symbolic)]
+#[symbol(alphabet = smol_symbol::Base32Crockford)]
+enum Digit {
+    #[symbol(rename = "O")]
+    Oh,
+    #[symbol(rename = "0")]
+    Zero,
+}
+
+static KEYS: SymbolSet<CustomSymbol<32, Base32Crockford>> =
+    symbol_set!(smol_symbol::Base32Crockford { abc, ABC });
+
+fn main() {
+    let sym = s!(ab, smol_symbol::Base32Crockford);
+    let _ = sym_match!(sym, smol_symbol::Base32Crockford {
+        "1" | "L" => 1,
+        _ => 0,
+    });
+}
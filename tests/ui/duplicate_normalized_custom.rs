@@ -0,0 +1,36 @@
+use smol_symbol::*;
+
+custom_alphabet!(
+    Cli,
+    abcdefghijklmnopqrstuvwxyz_,
+    normalize(fold_case, '-' | ' ' => '_', trim)
+);
+
+#[derive(Symbolic)]
+#[symbol(alphabet = Cli)]
+enum Op {
+    #[symbol(rename = "Start")]
+    Begin,
+    Start,
+}
+
+static KEYS: SymbolSet<CustomSymbol<27, Cli>> = symbol_set!(Cli { hello_world, "Hello-World" });
+
+fn main() {
+    let sym = s!(ab, Cli);
+    let _ = sym_match!(sym, Cli {
+        "AB" => 1,
+        ab => 2,
+        _ => 0,
+    });
+    let _ = sym_match!(sym, Cli {
+        ab | "Ab" => 1,
+        _ => 0,
+    });
+    // symbols in guarded arms can be matched again
+    let _ = sym_match!(sym, Cli {
+        ab if false => 1,
+        "AB" => 2,
+        _ => 0,
+    });
+}
@@ -0,0 +1,8 @@
+use smol_symbol::s;
+
+fn main() {
+    let _ = s!(user + "-" + created);
+    let _ = s!(user + "_" + Created);
+    let _ = s!(this_symbol_is + _much_too_long + _to_store);
+    let _ = s!(user +);
+}
@@ -0,0 +1,9 @@
+use smol_symbol::{custom_alphabet, s, CustomSymbol};
+
+custom_alphabet!(Digits, _0123456789);
+
+const SYM: CustomSymbol<11, Digits> = s!(_0123456789012345678901234567890123456789, Digits);
+
+fn main() {
+    let _ = SYM;
+}
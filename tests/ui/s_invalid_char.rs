@@ -0,0 +1,9 @@
+use smol_symbol::{s, Symbol};
+
+const SYM: Symbol = s!(hello_World);
+
+fn main() {
+    let _ = SYM;
+    let _ = s!(goodbye, smol_symbol::DefaultAlphabet);
+    let _ = s!(good9bye, smol_symbol::DefaultAlphabet);
+}
@@ -0,0 +1,7 @@
+use smol_symbol::s;
+
+fn main() {
+    let _ = s!("hello-world");
+    let _ = s!("");
+    let _ = s!("this_symbol_is_much_too_long_to_store");
+}
@@ -0,0 +1,5 @@
+use smol_symbol::*;
+
+fn main() {
+    let _ = s!(fourteen_chars, smol_symbol::DefaultAlphabet, u64);
+}
@@ -0,0 +1,5 @@
+use smol_symbol::s;
+
+fn main() {
+    let _ = s!(this_symbol_is_much_too_long_to_store);
+}
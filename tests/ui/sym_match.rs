@@ -0,0 +1,31 @@
+use smol_symbol::{s, sym_match};
+
+fn main() {
+    let sym = s!(hello);
+    let _ = sym_match!(sym {
+        hello => 1,
+        world => 2,
+    });
+    let _ = sym_match!(sym {
+        hello | world => 1,
+        world => 2,
+        _ => 3,
+    });
+    let _ = sym_match!(sym {
+        hello | world | "hello" => 1,
+        _ => 2,
+    });
+    let _ = sym_match!(sym {
+        hello => 1,
+        Hello => 2,
+        _ => 3,
+    });
+    let _ = sym_match!(sym {
+        _ => 1,
+        hello => 2,
+    });
+    let _ = sym_match!(sym {
+        Some(x) => 1,
+        _ => 2,
+    });
+}
@@ -0,0 +1,11 @@
+use smol_symbol::{custom_alphabet, s, sym_match};
+
+custom_alphabet!(Upper, ABCDEFGHIJKLMNOPQRSTUVWXYZ);
+
+fn main() {
+    let sym = s!(HELLO, Upper);
+    let _ = sym_match!(sym {
+        hello => 1,
+        _ => 2,
+    });
+}
@@ -0,0 +1,10 @@
+use smol_symbol::*;
+
+static DUPLICATE: SymbolMap<u8> = symbol_map! {
+    hello => 1,
+    "hello" => 2,
+};
+
+static INVALID: SymbolSet = symbol_set! { valid, Invalid };
+
+fn main() {}